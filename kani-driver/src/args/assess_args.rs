@@ -16,6 +16,10 @@ pub struct AssessArgs {
     /// Write Assess metadata (unstable file format) to the given file
     #[arg(long, hide = true)]
     pub emit_metadata: Option<PathBuf>,
+
+    /// Write the number and names of the targets that failed to build to the given JSON file
+    #[arg(long, hide = true)]
+    pub emit_failed_targets: Option<PathBuf>,
 }
 
 /// `cargo kani assess` takes optional subcommands to request specialized behavior
@@ -74,6 +74,24 @@ pub struct StandaloneArgs {
     #[arg(required = true)]
     pub input: Option<PathBuf>,
 
+    /// Other Rust files to verify together with the input file. Each file is compiled as a
+    /// separate crate.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"), value_name = "FILE")]
+    pub extra_input: Vec<PathBuf>,
+
+    /// Verify the input as a goto binary that was built outside of Kani, given the Kani metadata
+    /// file of its harnesses. The input is not compiled.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("enable_unstable"),
+        conflicts_with("extra_input"),
+        value_name = "METADATA_FILE"
+    )]
+    pub goto_metadata: Option<PathBuf>,
+
     #[command(flatten)]
     pub verify_opts: VerificationArgs,
 
@@ -149,6 +167,9 @@ pub struct VerificationArgs {
     pub gen_c: bool,
 
     /// Directory for all generated artifacts.
+    /// For `kani`, artifacts are written to a temporary directory by default, unless they are
+    /// meant to be kept (e.g.: with `--keep-temps`), in which case they are written next to the
+    /// input file.
     #[arg(long)]
     pub target_dir: Option<PathBuf>,
 
@@ -156,6 +177,51 @@ pub struct VerificationArgs {
     #[arg(long)]
     pub force_build: bool,
 
+    /// Skip the build if the sources of the packages being verified haven't changed since the
+    /// last build, and reuse its outputs instead. Only supported by `cargo kani`.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("enable_unstable"),
+        conflicts_with("force_build")
+    )]
+    pub reuse_unchanged_build: bool,
+
+    /// Record the digest of every harness model to the given file once all harnesses in the
+    /// project have been successfully verified.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"), value_name = "LOCK_FILE")]
+    pub write_verified_lock: Option<PathBuf>,
+
+    /// Fail if the model of any harness differs from the digest recorded in the given file.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"), value_name = "LOCK_FILE")]
+    pub check_verified_lock: Option<PathBuf>,
+
+    /// Remove the artifacts generated for the project once verification is done.
+    /// For `cargo kani`, the compiler outputs are kept, since cargo won't rebuild them.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub clean_artifacts: bool,
+
+    /// Include the harnesses from crates that were compiled without cargo, given their Kani
+    /// metadata file, when verifying a cargo project. This cannot be used with `--function`.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("enable_unstable"),
+        conflicts_with("function"),
+        value_name = "METADATA_FILE"
+    )]
+    pub standalone_metadata: Vec<PathBuf>,
+
+    /// List the harnesses of the project and exit, without linking or verifying them.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub list_harnesses: bool,
+
     /// Toggle between different styles of output
     #[arg(long, default_value = "regular", ignore_case = true, value_enum)]
     pub output_format: OutputFormat,
@@ -163,12 +229,15 @@ pub struct VerificationArgs {
     #[command(flatten)]
     pub checks: CheckArgs,
 
-    /// Entry point for verification (symbol name).
+    /// Entry point for verification (symbol name). This argument can be used multiple times to
+    /// verify more than one function.
     /// This is an unstable feature. Consider using --harness instead
-    #[arg(long, hide = true, requires("enable_unstable"))]
-    pub function: Option<String>,
+    #[arg(long, hide = true, requires("enable_unstable"), num_args(1))]
+    pub function: Vec<String>,
     /// If specified, only run harnesses that match this filter. This option can be provided
     /// multiple times, which will run all tests matching any of the filters.
+    /// Filters that contain wildcards (`*`, `?` or `[`) are also matched as glob patterns.
+    /// A harness name can be prefixed by its crate name, e.g.: `my_crate::harness`.
     /// If used with --exact, the harness filter will only match the exact fully qualified name of a harness.
     #[arg(
         long = "harness",
@@ -334,6 +403,12 @@ pub fn jobs(&self) -> Option<usize> {
         }
     }
 
+    /// Computes how many threads should be used to link goto binaries. Unlike verification,
+    /// linking uses every available core unless the user provided a number of jobs.
+    pub fn link_jobs(&self) -> Option<usize> {
+        self.jobs.flatten()
+    }
+
     /// Are experimental function contracts enabled?
     pub fn is_function_contracts_enabled(&self) -> bool {
         self.common_args.unstable_features.contains(UnstableFeature::FunctionContracts)
@@ -449,7 +524,13 @@ fn validate(&self) -> Result<(), Error> {
         check_no_cargo_opt(!self.verify_opts.cargo.exclude.is_empty(), "--exclude")?;
         check_no_cargo_opt(self.verify_opts.cargo.workspace, "--workspace")?;
         check_no_cargo_opt(self.verify_opts.cargo.manifest_path.is_some(), "--manifest-path")?;
-        if let Some(input) = &self.input {
+        check_no_cargo_opt(self.verify_opts.list_harnesses, "--list-harnesses")?;
+        check_no_cargo_opt(self.verify_opts.reuse_unchanged_build, "--reuse-unchanged-build")?;
+        check_no_cargo_opt(
+            !self.verify_opts.standalone_metadata.is_empty(),
+            "--standalone-metadata",
+        )?;
+        for input in self.input.iter().chain(&self.extra_input).chain(&self.goto_metadata) {
             if !input.is_file() {
                 return Err(Error::raw(
                     ErrorKind::InvalidValue,
@@ -730,6 +811,55 @@ fn check_multiple_harnesses() {
         assert_eq!(args.verify_opts.harnesses, vec!["a".to_owned(), "b".to_owned()]);
     }
 
+    /// Ensure users can pass multiple function options and that the value is accumulated.
+    #[test]
+    fn check_multiple_functions() {
+        let args = parse_unstable_enabled("--function foo --function bar").unwrap();
+        assert_eq!(args.verify_opts.function, ["foo", "bar"]);
+    }
+
+    #[test]
+    fn check_standalone_metadata() {
+        let args = vec!["cargo-kani", "--enable-unstable", "--standalone-metadata", "a.json"];
+        let args = CargoKaniArgs::try_parse_from(args).unwrap();
+        assert_eq!(args.verify_opts.standalone_metadata, [PathBuf::from("a.json")]);
+
+        // The harnesses of standalone crates cannot be combined with `--function`.
+        let args = "cargo-kani --enable-unstable --standalone-metadata a.json --function foo";
+        let err = CargoKaniArgs::try_parse_from(args.split(' ')).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+
+        // Standalone Kani builds every input itself.
+        let args = parse_unstable_enabled("--standalone-metadata a.json").unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::UnknownArgument);
+    }
+
+    #[test]
+    fn check_list_harnesses() {
+        let args = vec!["cargo-kani", "--enable-unstable", "--list-harnesses"];
+        assert!(CargoKaniArgs::try_parse_from(args).unwrap().verify_opts.list_harnesses);
+        let result = CargoKaniArgs::try_parse_from(vec!["cargo-kani", "--list-harnesses"]);
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+
+        // Listing harnesses is only supported for cargo projects.
+        let err = parse_unstable_enabled("--list-harnesses").unwrap().validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownArgument);
+    }
+
+    #[test]
+    fn check_reuse_unchanged_build() {
+        let args = vec!["cargo-kani", "--enable-unstable", "--reuse-unchanged-build"];
+        assert!(CargoKaniArgs::try_parse_from(args).unwrap().verify_opts.reuse_unchanged_build);
+        let args =
+            vec!["cargo-kani", "--enable-unstable", "--reuse-unchanged-build", "--force-build"];
+        let err = CargoKaniArgs::try_parse_from(args).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+
+        let err =
+            parse_unstable_enabled("--reuse-unchanged-build").unwrap().validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownArgument);
+    }
+
     #[test]
     fn check_multiple_harnesses_without_flag_fail() {
         let result = StandaloneArgs::try_parse_from(
@@ -797,6 +927,33 @@ fn check_invalid_input_fails() {
         assert_eq!(err.kind(), ErrorKind::InvalidValue);
     }
 
+    /// Kani should accept extra input files and validate each one of them.
+    #[test]
+    fn check_extra_input() {
+        let args =
+            parse_unstable_enabled("--extra-input Cargo.toml --extra-input build.rs").unwrap();
+        assert_eq!(args.extra_input, [PathBuf::from("Cargo.toml"), PathBuf::from("build.rs")]);
+
+        let args = vec!["kani", "--enable-unstable", "build.rs", "--extra-input", "."];
+        let args = StandaloneArgs::try_parse_from(args).unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_goto_metadata() {
+        let args = parse_unstable_enabled("--goto-metadata build.rs").unwrap();
+        assert_eq!(args.goto_metadata, Some(PathBuf::from("build.rs")));
+
+        let args = vec!["kani", "--enable-unstable", "build.rs", "--goto-metadata", "."];
+        let args = StandaloneArgs::try_parse_from(args).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+
+        let args = parse_unstable_enabled("--goto-metadata build.rs --extra-input Cargo.toml")
+            .unwrap_err();
+        assert_eq!(args.kind(), ErrorKind::ArgumentConflict);
+    }
+
     #[test]
     fn check_unwind_conflicts() {
         // --unwind cannot be called without --harness
@@ -815,6 +972,18 @@ fn parse_unstable_enabled(args: &str) -> Result<StandaloneArgs, Error> {
         StandaloneArgs::try_parse_from(args.split(' '))
     }
 
+    #[test]
+    fn check_jobs() {
+        let jobs = |args| {
+            let args = parse_unstable_enabled(args).unwrap().verify_opts;
+            (args.jobs(), args.link_jobs())
+        };
+        // Verification is serial by default, while linking uses every available core.
+        assert_eq!(jobs("--output-format terse"), (Some(1), None));
+        assert_eq!(jobs("--output-format terse -j"), (None, None));
+        assert_eq!(jobs("--output-format terse -j 4"), (Some(4), Some(4)));
+    }
+
     #[test]
     fn check_restrict_vtable_unstable() {
         check_unstable_flag!("--restrict-vtable", restrict_vtable);
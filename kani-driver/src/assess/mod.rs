@@ -27,7 +27,7 @@ pub(crate) fn run_assess(session: KaniSession, args: AssessArgs) -> Result<()> {
         return scan::assess_scan_main(session, args);
     }
 
-    let result = assess_project(session);
+    let result = assess_project(session, &args);
     match result {
         Ok(metadata) => write_metadata(&args, metadata),
         Err(err) => {
@@ -38,7 +38,7 @@ pub(crate) fn run_assess(session: KaniSession, args: AssessArgs) -> Result<()> {
     }
 }
 
-fn assess_project(mut session: KaniSession) -> Result<AssessMetadata> {
+fn assess_project(mut session: KaniSession, args: &AssessArgs) -> Result<AssessMetadata> {
     // Fix (as in "make unchanging/unchangable") some settings.
     // This is a temporary hack to make things work, until we get around to refactoring how arguments
     // work generally in kani-driver. These arguments, for instance, are all prepended to the subcommand,
@@ -72,6 +72,9 @@ fn assess_project(mut session: KaniSession) -> Result<AssessMetadata> {
     // Tracking for the latter: https://github.com/model-checking/kani/issues/1758
 
     let build_fail = project.failed_targets.as_ref().unwrap();
+    if let Some(path) = &args.emit_failed_targets {
+        std::fs::write(path, project.failed_targets_report().unwrap())?;
+    }
     match (build_fail.len(), packages_metadata.len()) {
         (0, 0) => println!("No relevant data was found."),
         (0, succeeded) => println!("Analyzed {succeeded} packages"),
@@ -3,13 +3,14 @@
 
 use crate::args::VerificationArgs;
 use crate::call_single_file::to_rustc_arg;
-use crate::project::Artifact;
+use crate::project::{ensure_outdir, Artifact};
 use crate::session::KaniSession;
 use crate::{session, util};
 use anyhow::{bail, Context, Result};
 use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
-use cargo_metadata::{Message, Metadata, MetadataCommand, Package, Target};
+use cargo_metadata::{Message, Metadata, MetadataCommand, Package, PackageId, Target};
 use kani_metadata::{ArtifactType, CompilerArtifactStub};
+use sha2::{Digest, Sha256};
 use std::ffi::{OsStr, OsString};
 use std::fmt::{self, Display};
 use std::fs::{self, File};
@@ -31,7 +32,7 @@
 
 /// The outputs of kani-compiler being invoked via cargo on a project.
 pub struct CargoOutputs {
-    /// The directory where compiler outputs should be directed.
+    /// The directory where compiler outputs should be directed, in its canonical form.
     /// Usually 'target/BUILD_TRIPLE/debug/deps/'
     pub outdir: PathBuf,
     /// The kani-metadata.json files written by kani-compiler.
@@ -42,9 +43,64 @@ pub struct CargoOutputs {
     pub failed_targets: Option<Vec<String>>,
 }
 
+/// Everything that determines the outputs of a cargo build, which is computed before invoking
+/// cargo.
+pub struct CargoBuildPlan {
+    /// The cargo metadata of the workspace.
+    pub metadata: Metadata,
+    /// The target directory used by Kani, i.e., `<target_dir>/kani`.
+    pub target_dir: PathBuf,
+    /// The directory where compiler outputs should be directed, in its canonical form.
+    pub outdir: PathBuf,
+    /// The arguments passed to cargo for every package.
+    cargo_args: Vec<OsString>,
+    /// The flags passed to the compiler.
+    rustc_args: Vec<OsString>,
+    /// The arguments that are only passed to the target packages.
+    pkg_args: Vec<String>,
+    /// The packages to be verified.
+    packages: Vec<PackageId>,
+    /// Whether to keep building the remaining targets after a target fails to compile.
+    keep_going: bool,
+}
+
+impl CargoBuildPlan {
+    /// The packages to be verified.
+    pub fn packages(&self) -> Vec<&Package> {
+        self.packages
+            .iter()
+            .map(|id| self.metadata.packages.iter().find(|package| package.id == *id).unwrap())
+            .collect()
+    }
+
+    /// Compute the key used to identify the arguments of the build, including which packages are
+    /// built and the version of Kani that built them.
+    ///
+    /// The key is stored across runs, so it must not depend on the process that computed it.
+    pub fn args_key(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        for arg in self.cargo_args.iter().chain(&self.rustc_args) {
+            hasher.update(b"\0arg\0");
+            hasher.update(arg.to_string_lossy().as_bytes());
+        }
+        for arg in &self.pkg_args {
+            hasher.update(b"\0pkg_arg\0");
+            hasher.update(arg);
+        }
+        for package in &self.packages {
+            hasher.update(b"\0package\0");
+            hasher.update(&package.repr);
+        }
+        hasher.update(if self.keep_going { "\0keep_going" } else { "\0stop" });
+        format!("{:x}", hasher.finalize())
+    }
+}
+
 impl KaniSession {
-    /// Calls `cargo_build` to generate `*.symtab.json` files in `target_dir`
-    pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
+    /// Compute the arguments of a cargo build and which packages it builds, without building
+    /// anything.
+    pub fn cargo_build_plan(&self, keep_going: bool) -> Result<CargoBuildPlan> {
         let build_target = env!("TARGET"); // see build.rs
         let metadata = self.cargo_metadata(build_target)?;
         let target_dir = self
@@ -54,11 +110,10 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
             .unwrap_or(&metadata.target_directory.clone().into())
             .clone()
             .join("kani");
-        let outdir = target_dir.join(build_target).join("debug/deps");
-
         if self.args.force_build && target_dir.exists() {
             fs::remove_dir_all(&target_dir)?;
         }
+        let outdir = ensure_outdir(&target_dir.join(build_target).join("debug/deps"))?;
 
         let mut rustc_args = self.kani_rustc_flags();
         rustc_args.push(to_rustc_arg(self.kani_compiler_flags()).into());
@@ -82,7 +137,7 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
         cargo_args.append(&mut cargo_config_args());
 
         cargo_args.push("--target-dir".into());
-        cargo_args.push(target_dir.into());
+        cargo_args.push(target_dir.clone().into());
 
         // Configuration needed to parse cargo compilation status.
         cargo_args.push("--message-format".into());
@@ -103,22 +158,41 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
         let mut pkg_args: Vec<String> = vec![];
         pkg_args.extend(["--".to_string(), self.reachability_arg()]);
 
+        let packages = packages_to_verify(&self.args, &metadata)?
+            .into_iter()
+            .map(|package| package.id.clone())
+            .collect();
+        Ok(CargoBuildPlan {
+            metadata,
+            target_dir,
+            outdir,
+            cargo_args,
+            rustc_args,
+            pkg_args,
+            packages,
+            keep_going,
+        })
+    }
+
+    /// Calls `cargo_build` to generate `*.symtab.json` files in `target_dir` for every package in
+    /// the given plan.
+    pub fn cargo_build(&self, plan: CargoBuildPlan) -> Result<CargoOutputs> {
+        let keep_going = plan.keep_going;
         let mut found_target = false;
-        let packages = packages_to_verify(&self.args, &metadata)?;
         let mut artifacts = vec![];
         let mut failed_targets = vec![];
-        for package in packages {
+        for package in plan.packages() {
             for verification_target in package_targets(&self.args, package) {
                 let mut cmd = Command::new("cargo");
                 cmd.arg(session::toolchain_shorthand())
-                    .args(&cargo_args)
+                    .args(&plan.cargo_args)
                     .args(vec!["-p", &package.name])
                     .args(&verification_target.to_args())
-                    .args(&pkg_args)
+                    .args(&plan.pkg_args)
                     .env("RUSTC", &self.kani_compiler)
                     // Use CARGO_ENCODED_RUSTFLAGS instead of RUSTFLAGS is preferred. See
                     // https://doc.rust-lang.org/cargo/reference/environment-variables.html
-                    .env("CARGO_ENCODED_RUSTFLAGS", rustc_args.join(OsStr::new("\x1f")))
+                    .env("CARGO_ENCODED_RUSTFLAGS", plan.rustc_args.join(OsStr::new("\x1f")))
                     .env("CARGO_TERM_PROGRESS_WHEN", "never");
 
                 match self.run_cargo(cmd, verification_target.target()) {
@@ -143,9 +217,9 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
         }
 
         Ok(CargoOutputs {
-            outdir,
+            outdir: plan.outdir,
             metadata: artifacts,
-            cargo_metadata: metadata,
+            cargo_metadata: plan.metadata,
             failed_targets: keep_going.then_some(failed_targets),
         })
     }
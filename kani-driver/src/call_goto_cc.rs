@@ -1,35 +1,85 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::ffi::OsString;
+use std::fs::File;
+use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use tracing::debug;
 
+use crate::args::common::Verbosity;
+use crate::metadata::from_json;
 use crate::session::KaniSession;
+use crate::util::file_digest;
 
 impl KaniSession {
     /// Given a set of goto binaries (`inputs`), produce `output` by linking everything
     /// together (including essential libraries). The result is generic over all proof harnesses.
-    pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<()> {
+    /// Returns the output produced by the linker, which may include warnings. In verbose mode, the
+    /// output is also printed as the linker runs.
+    pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<String> {
         let mut args: Vec<OsString> = Vec::new();
         args.extend(inputs.iter().map(|x| x.clone().into_os_string()));
-        args.extend(self.args.c_lib.iter().map(|x| x.clone().into_os_string()));
-
-        // TODO think about this: kani_lib_c is just an empty c file. Maybe we could just
-        // create such an empty file ourselves instead of having to look up this path.
-        args.push(self.kani_lib_c.clone().into_os_string());
+        args.extend(self.link_flags());
 
         args.push("-o".into());
         args.push(output.to_owned().into_os_string());
 
-        // TODO get goto-cc path from self
-        let mut cmd = Command::new("goto-cc");
+        let mut cmd = Command::new(&self.goto_cc);
         cmd.args(args);
 
-        self.run_suppress(cmd)?;
+        self.run_capture(cmd)
+    }
 
-        Ok(())
+    /// Same as `link_goto_binary`, but reuse `output` if it was linked from the same inputs with
+    /// the same linker flags, and it hasn't been modified since.
+    ///
+    /// The information needed to validate the cached output is stored in a file next to it.
+    pub fn link_goto_binary_cached(&self, inputs: &[PathBuf], output: &Path) -> Result<String> {
+        let key = link_cache_key(self.goto_cc_version()?, inputs, &self.link_flags())?;
+        let cache_file = link_cache_file(output);
+        if let Some(entry) = LinkCacheEntry::read_fresh(&cache_file, &key, output) {
+            debug!(?output, "reuse cached goto binary");
+            if self.args.common_args.verbose() {
+                println!("[Kani] Reusing `{}` linked by a previous run", output.display());
+                print!("{}", entry.link_log);
+            }
+            return Ok(entry.link_log);
+        }
+        let link_log = self.link_goto_binary(inputs, output)?;
+        let entry = LinkCacheEntry { key, output_digest: file_digest(output)?, link_log };
+        entry.write(&cache_file)?;
+        Ok(entry.link_log)
+    }
+
+    /// The version reported by `goto-cc`, which is only queried once per session.
+    fn goto_cc_version(&self) -> Result<&str> {
+        let version = self.goto_cc_version.get_or_try_init(|| -> Result<String> {
+            let output = Command::new(&self.goto_cc)
+                .arg("--version")
+                .output()
+                .with_context(|| format!("Failed to invoke {}", self.goto_cc.display()))?;
+            if !output.status.success() {
+                bail!("Failed to query the version of {}", self.goto_cc.display());
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })?;
+        Ok(version)
+    }
+
+    /// The arguments passed to the linker in addition to the goto binaries being linked.
+    fn link_flags(&self) -> Vec<OsString> {
+        let mut flags: Vec<OsString> =
+            self.args.c_lib.iter().map(|x| x.clone().into_os_string()).collect();
+
+        // TODO think about this: kani_lib_c is just an empty c file. Maybe we could just
+        // create such an empty file ourselves instead of having to look up this path.
+        flags.push(self.kani_lib_c.clone().into_os_string());
+        flags
     }
 
     /// Produce a goto binary with its entry point set to a particular proof harness.
@@ -39,7 +89,7 @@ pub fn specialize_to_proof_harness(
         output: &Path,
         function: &str,
     ) -> Result<()> {
-        let mut cmd = Command::new("goto-cc");
+        let mut cmd = Command::new(&self.goto_cc);
         cmd.arg(input).args(["--function", function, "-o"]).arg(output);
 
         self.run_suppress(cmd)?;
@@ -47,3 +97,100 @@ pub fn specialize_to_proof_harness(
         Ok(())
     }
 }
+
+/// The path of the file that stores the link cache information of the given goto binary.
+pub fn link_cache_file(output: &Path) -> PathBuf {
+    let mut path = output.as_os_str().to_owned();
+    path.push(".link-cache.json");
+    PathBuf::from(path)
+}
+
+/// Compute the key used to identify a link command. This includes the version of the linker, the
+/// content of the inputs as well as the linker flags.
+///
+/// The key is stored across runs, so it must not depend on the process that computed it.
+fn link_cache_key(version: &str, inputs: &[PathBuf], flags: &[OsString]) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(version);
+    for input in inputs {
+        hasher.update(b"\0input\0");
+        hasher.update(file_digest(input)?);
+    }
+    for flag in flags {
+        hasher.update(b"\0flag\0");
+        hasher.update(flag.to_string_lossy().as_bytes());
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Information stored after linking a goto binary.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct LinkCacheEntry {
+    /// The key of the link command that produced the goto binary.
+    key: String,
+    /// The digest of the goto binary right after it was linked, so we can detect if it was
+    /// modified since.
+    output_digest: String,
+    /// The output produced by the linker.
+    link_log: String,
+}
+
+impl LinkCacheEntry {
+    /// Read the cache entry from the given file, and return it only if it was created with the
+    /// given key and the goto binary hasn't been modified since.
+    fn read_fresh(cache_file: &Path, key: &str, output: &Path) -> Option<Self> {
+        let entry: LinkCacheEntry = from_json(cache_file).ok()?;
+        let is_fresh = entry.key == key && file_digest(output).ok()? == entry.output_digest;
+        is_fresh.then_some(entry)
+    }
+
+    fn write(&self, cache_file: &Path) -> Result<()> {
+        let writer = BufWriter::new(File::create(cache_file)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_link_cache_invalidation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let input = dir.join("krate.symtab.out");
+        let output = dir.join("krate.out");
+        let cache_file = link_cache_file(&output);
+        let flags = [OsString::from("kani_lib.c")];
+        std::fs::write(&input, "model").unwrap();
+        std::fs::write(&output, "linked").unwrap();
+
+        // A new entry is fresh for the same inputs and flags.
+        let key = link_cache_key("5.95.1", &[input.clone()], &flags).unwrap();
+        let entry = LinkCacheEntry {
+            key: key.clone(),
+            output_digest: file_digest(&output).unwrap(),
+            link_log: "warning".to_string(),
+        };
+        entry.write(&cache_file).unwrap();
+        assert_eq!(LinkCacheEntry::read_fresh(&cache_file, &key, &output), Some(entry));
+
+        // Changing the linker flags changes the key.
+        let other_flags = [OsString::from("stubs.c"), OsString::from("kani_lib.c")];
+        assert_ne!(link_cache_key("5.95.1", &[input.clone()], &other_flags).unwrap(), key);
+
+        // Changing the linker version changes the key.
+        assert_ne!(link_cache_key("5.96.0", &[input.clone()], &flags).unwrap(), key);
+
+        // Changing the input changes the key.
+        std::fs::write(&input, "new model").unwrap();
+        let new_key = link_cache_key("5.95.1", &[input.clone()], &flags).unwrap();
+        assert_ne!(new_key, key);
+        assert_eq!(LinkCacheEntry::read_fresh(&cache_file, &new_key, &output), None);
+
+        // Modifying the output invalidates the entry.
+        std::fs::write(&output, "instrumented").unwrap();
+        assert_eq!(LinkCacheEntry::read_fresh(&cache_file, &key, &output), None);
+    }
+}
@@ -11,11 +11,12 @@
 use crate::metadata::collect_and_link_function_pointer_restrictions;
 use crate::project::Project;
 use crate::session::KaniSession;
-use crate::util::alter_extension;
+use crate::util::{alter_extension, warning};
 use kani_metadata::{ArtifactType, HarnessMetadata};
 
 impl KaniSession {
-    /// Instrument and optimize a goto binary in-place.
+    /// Instrument and optimize the goto binary `input` for the given harness, writing the result to
+    /// `output`.
     pub fn instrument_model(
         &self,
         input: &Path,
@@ -47,6 +48,8 @@ pub fn instrument_model(
         self.rewrite_back_edges(output)?;
 
         if self.args.gen_c {
+            // Name the C files after the instrumented model, since the input may be shared by
+            // other harnesses.
             let c_outfile = alter_extension(output, "c");
             // We don't put the C file into temporaries to be deleted.
 
@@ -56,12 +59,22 @@ pub fn instrument_model(
                 println!("Generated C code written to {}", c_outfile.to_string_lossy());
             }
 
-            let c_demangled = alter_extension(output, "demangled.c");
-            let prett_name_map =
-                project.get_harness_artifact(&harness, ArtifactType::PrettyNameMap).unwrap();
-            self.demangle_c(prett_name_map, &c_outfile, &c_demangled)?;
-            if !self.args.common_args.quiet {
-                println!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
+            // Goto binaries that were not built by Kani, e.g. with `--goto-metadata`, have no
+            // pretty name map, so we cannot demangle their C code.
+            let pretty_name_map =
+                project.get_harness_artifact(&harness, ArtifactType::PrettyNameMap);
+            if let Some(pretty_name_map) = pretty_name_map {
+                let c_demangled = alter_extension(output, "demangled.c");
+                self.demangle_c(pretty_name_map, &c_outfile, &c_demangled)?;
+                if !self.args.common_args.quiet {
+                    println!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
+                }
+            } else if !self.args.common_args.quiet {
+                warning(&format!(
+                    "Skipped demangling {} since no pretty name map was found for harness `{}`.",
+                    c_outfile.display(),
+                    harness.pretty_name
+                ));
             }
         }
 
@@ -190,8 +203,7 @@ pub fn demangle_c(
 
     /// Non-public helper function to actually do the run of goto-instrument
     fn call_goto_instrument(&self, args: Vec<OsString>) -> Result<()> {
-        // TODO get goto-instrument path from self
-        let mut cmd = Command::new("goto-instrument");
+        let mut cmd = Command::new(&self.goto_instrument);
         cmd.args(args);
 
         self.run_suppress(cmd)
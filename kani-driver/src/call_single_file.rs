@@ -36,7 +36,7 @@ pub fn compile_single_rust_file(
             }
         } else {
             // If we specifically request "--function main" then don't override crate type
-            if Some("main".to_string()) != self.args.function {
+            if !self.args.function.iter().any(|function| function == "main") {
                 // We only run against proof harnesses normally, and this change
                 // 1. Means we do not require a `fn main` to exist
                 // 2. Don't forget it also changes visibility rules.
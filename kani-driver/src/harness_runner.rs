@@ -4,13 +4,14 @@
 use anyhow::{bail, Result};
 use kani_metadata::{ArtifactType, HarnessMetadata};
 use rayon::prelude::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::args::common::Verbosity;
 use crate::args::OutputFormat;
 use crate::call_cbmc::{VerificationResult, VerificationStatus};
 use crate::project::Project;
 use crate::session::KaniSession;
-use crate::util::error;
+use crate::util::{alter_extension, error, thread_pool};
 
 /// A HarnessRunner is responsible for checking all proof harnesses. The data in this structure represents
 /// "background information" that the controlling driver (e.g. cargo-kani or kani) computed.
@@ -41,29 +42,25 @@ pub(crate) fn check_all_harnesses(
 
         let sorted_harnesses = crate::metadata::sort_harnesses_by_loc(harnesses);
 
-        let pool = {
-            let mut builder = rayon::ThreadPoolBuilder::new();
-            if let Some(x) = self.sess.args.jobs() {
-                builder = builder.num_threads(x);
-            }
-            builder.build()?
-        };
+        let pool = thread_pool(self.sess.args.jobs())?;
 
         let results = pool.install(|| -> Result<Vec<HarnessResult<'pr>>> {
             sorted_harnesses
                 .par_iter()
                 .map(|harness| -> Result<HarnessResult<'pr>> {
-                    let harness_filename = harness.pretty_name.replace("::", "-");
-                    let report_dir = self.project.outdir.join(format!("report-{harness_filename}"));
-                    let goto_file =
-                        self.project.get_harness_artifact(&harness, ArtifactType::Goto).unwrap();
-                    self.sess.instrument_model(goto_file, goto_file, &self.project, &harness)?;
-
-                    if self.sess.args.synthesize_loop_contracts {
-                        self.sess.synthesize_loop_contracts(goto_file, &goto_file, &harness)?;
+                    let report_dir =
+                        self.project.outdir.join(format!("report-{}", harness_filename(harness)));
+                    // In verbose mode, the link output was already printed while linking.
+                    let common_args = &self.sess.args.common_args;
+                    if !common_args.quiet
+                        && !common_args.verbose()
+                        && let Some(log) = self.project.get_harness_link_log(harness)
+                        && !log.is_empty()
+                    {
+                        println!("Link output for harness {}:\n{log}", harness.pretty_name);
                     }
-
-                    let result = self.sess.check_harness(goto_file, &report_dir, harness)?;
+                    let goto_file = self.instrument_harness(harness)?;
+                    let result = self.sess.check_harness(&goto_file, &report_dir, harness)?;
                     Ok(HarnessResult { harness, result })
                 })
                 .collect::<Result<Vec<_>>>()
@@ -72,6 +69,23 @@ pub(crate) fn check_all_harnesses(
         Ok(results)
     }
 
+    /// Produce the goto binary used to verify the given harness.
+    ///
+    /// The model linked for the harness may be shared by other harnesses and reused by later runs,
+    /// so we instrument it into a separate file instead of modifying it in place.
+    pub(crate) fn instrument_harness(&self, harness: &HarnessMetadata) -> Result<PathBuf> {
+        let goto_file = self.project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
+        let instrumented_file =
+            alter_extension(goto_file, &format!("{}.out", harness_filename(harness)));
+        self.sess.record_temporary_file(&instrumented_file);
+        self.sess.instrument_model(goto_file, &instrumented_file, self.project, harness)?;
+
+        if self.sess.args.synthesize_loop_contracts {
+            self.sess.synthesize_loop_contracts(&instrumented_file, &instrumented_file, harness)?;
+        }
+        Ok(instrumented_file)
+    }
+
     /// Return an error if the user is trying to verify a harness with stubs without enabling the
     /// experimental feature.
     fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
@@ -100,6 +114,11 @@ fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
     }
 }
 
+/// The name used for files that are specific to the given harness.
+fn harness_filename(harness: &HarnessMetadata) -> String {
+    harness.pretty_name.replace("::", "-")
+}
+
 impl KaniSession {
     /// Run the verification process for a single harness
     pub(crate) fn check_harness(
@@ -172,8 +191,8 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
                     "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
                 );
             } else {
-                match (self.args.harnesses.as_slice(), &self.args.function) {
-                    ([], None) =>
+                match (self.args.harnesses.as_slice(), self.args.function.as_slice()) {
+                    ([], []) =>
                     // TODO: This could use a better message, possibly with links to Kani documentation.
                     // New users may encounter this and could use a pointer to how to write proof harnesses.
                     {
@@ -181,14 +200,17 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
                             "No proof harnesses (functions with #[kani::proof]) were found to verify."
                         )
                     }
-                    ([harness], None) => {
+                    ([harness], []) => {
                         bail!("no harnesses matched the harness filter: `{harness}`")
                     }
-                    (harnesses, None) => bail!(
+                    (harnesses, []) => bail!(
                         "no harnesses matched the harness filters: `{}`",
                         harnesses.join("`, `")
                     ),
-                    ([], Some(func)) => error(&format!("No function named {func} was found")),
+                    ([], [func]) => error(&format!("No function named {func} was found")),
+                    ([], funcs) => {
+                        error(&format!("No functions named {} were found", funcs.join(", ")))
+                    }
                     _ => unreachable!(
                         "invalid configuration. Cannot specify harness and function at the same time"
                     ),
@@ -10,12 +10,15 @@
 use args::{check_is_valid, CargoKaniSubcommand};
 use args_toml::join_args;
 
+use crate::args::common::Verbosity;
 use crate::args::StandaloneSubcommand;
+use crate::call_cbmc::VerificationStatus;
 use crate::concrete_playback::playback::{playback_cargo, playback_standalone};
 use crate::project::Project;
 use crate::session::KaniSession;
 use crate::version::print_kani_version;
 use clap::Parser;
+use kani_metadata::ArtifactType;
 use tracing::debug;
 
 mod args;
@@ -86,7 +89,23 @@ fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
         return assess::run_assess(session, assess::AssessArgs::default());
     }
 
+    if session.args.list_harnesses {
+        for harness in project::cargo_harnesses(&session, false)? {
+            println!(
+                "{}::{} ({}:{})",
+                harness.crate_name,
+                harness.pretty_name,
+                harness.original_file,
+                harness.original_start_line
+            );
+        }
+        return Ok(());
+    }
+
     let project = project::cargo_project(&session, false)?;
+    if let Some(lock) = &session.args.check_verified_lock {
+        project.check_verified_lock(lock)?;
+    }
     if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
 }
 
@@ -105,20 +124,52 @@ fn standalone_main() -> Result<()> {
         print_kani_version(InvocationType::Standalone);
     }
 
-    let project = project::standalone_project(&args.input.unwrap(), &session)?;
+    let inputs: Vec<_> = args.input.into_iter().chain(args.extra_input).collect();
+    let project = if let Some(md_file) = &args.goto_metadata {
+        project::goto_project(&inputs[0], md_file, &session)?
+    } else {
+        project::standalone_project(&inputs, &session)?
+    };
+    if let Some(lock) = &session.args.check_verified_lock {
+        project.check_verified_lock(lock)?;
+    }
     if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
 }
 
 /// Run verification on the given project.
 fn verify_project(project: Project, session: KaniSession) -> Result<()> {
     debug!(?project, "verify_project");
-    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let harnesses = session.determine_targets(&project)?;
     debug!(n = harnesses.len(), ?harnesses, "verify_project");
+    if session.args.common_args.verbose() {
+        match project.total_artifact_size(ArtifactType::Goto) {
+            Ok(size) => println!("Total size of the goto binaries: {size} bytes"),
+            Err(err) => util::warning(&format!("Failed to compute the goto binaries size: {err}")),
+        }
+        println!("Total build time: {:.3}s", project.build_timings.total().as_secs_f64());
+    }
 
     // Verification
     let runner = harness_runner::HarnessRunner { sess: &session, project: &project };
     let results = runner.check_all_harnesses(&harnesses)?;
 
+    if let Some(lock) = &session.args.write_verified_lock {
+        let all_verified = results.len() == project.get_all_harnesses().len()
+            && results.iter().all(|r| r.result.status == VerificationStatus::Success);
+        if all_verified {
+            project.write_verified_lock(lock)?;
+        } else if !session.args.common_args.quiet {
+            util::warning(&format!(
+                "Skipped writing `{}` since not every harness was successfully verified.",
+                lock.display()
+            ));
+        }
+    }
+
+    if session.args.clean_artifacts {
+        project.clean()?;
+    }
+
     session.print_final_summary(&results)
 }
 
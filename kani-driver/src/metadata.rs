@@ -13,6 +13,7 @@
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 
+use crate::project::Project;
 use crate::session::KaniSession;
 use serde::Deserialize;
 
@@ -111,42 +112,42 @@ pub fn merge_kani_metadata(files: Vec<KaniMetadata>) -> KaniMetadata {
 
 impl KaniSession {
     /// Determine which function to use as entry point, based on command-line arguments and kani-metadata.
-    pub fn determine_targets<'a>(
-        &self,
-        all_harnesses: &[&'a HarnessMetadata],
-    ) -> Result<Vec<&'a HarnessMetadata>> {
+    pub fn determine_targets<'a>(&self, project: &'a Project) -> Result<Vec<&'a HarnessMetadata>> {
+        let all_harnesses = project.get_all_harnesses();
         let harnesses = if self.args.harnesses.is_empty() {
             BTreeSet::from_iter(self.args.function.iter())
         } else {
             BTreeSet::from_iter(self.args.harnesses.iter())
         };
 
-        let total_harnesses = harnesses.len();
-        let all_targets = &harnesses;
-
         if harnesses.is_empty() {
-            Ok(Vec::from(all_harnesses))
+            Ok(all_harnesses)
         } else {
-            let harnesses_found: Vec<&HarnessMetadata> =
-                find_proof_harnesses(&harnesses, all_harnesses, self.args.exact);
-
-            // If even one harness was not found with --exact, return an error to user
-            if self.args.exact && harnesses_found.len() < total_harnesses {
-                let harness_found_names: BTreeSet<&String> =
-                    harnesses_found.iter().map(|&h| &h.pretty_name).collect();
+            let mut harnesses_found: Vec<&HarnessMetadata> =
+                find_proof_harnesses(&harnesses, &all_harnesses, self.args.exact);
 
-                // Check which harnesses are missing from the difference of targets and harnesses_found
-                let harnesses_missing: Vec<&String> =
-                    all_targets.difference(&harness_found_names).cloned().collect();
-                let joined_string = harnesses_missing
+            if self.args.exact {
+                // If even one harness was not found with --exact, return an error to user
+                let harnesses_missing: Vec<&str> = harnesses
                     .iter()
-                    .map(|&s| (*s).clone())
-                    .collect::<Vec<String>>()
-                    .join("`, `");
-
-                bail!(
-                    "Failed to match the following harness(es):\n{joined_string}\nPlease specify the fully-qualified name of a harness.",
-                );
+                    .filter(|target| project.get_harness(target).is_none())
+                    .map(|target| target.as_str())
+                    .collect();
+                if !harnesses_missing.is_empty() {
+                    let joined_string = harnesses_missing.join("`, `");
+                    bail!(
+                        "Failed to match the following harness(es):\n{joined_string}\nPlease specify the fully-qualified name of a harness.",
+                    );
+                }
+            } else {
+                // Filters that include wildcards are also matched as glob patterns.
+                for pattern in harnesses.iter().filter(|target| target.contains(['*', '?', '['])) {
+                    for harness in project.get_harnesses_matching(pattern)? {
+                        if !harnesses_found.contains(&harness) {
+                            harnesses_found.push(harness);
+                        }
+                    }
+                }
             }
 
             Ok(harnesses_found)
@@ -200,7 +201,10 @@ fn find_proof_harnesses<'a>(
     for md in all_harnesses.iter() {
         if exact_filter {
             // Check for exact match only
-            if targets.contains(&md.pretty_name) {
+            if targets.contains(&md.pretty_name)
+                || targets.contains(&md.mangled_name)
+                || targets.iter().any(|target| is_crate_qualified_name(md, target))
+            {
                 // if exact match found, stop searching
                 result.push(*md);
             } else {
@@ -211,6 +215,7 @@ fn find_proof_harnesses<'a>(
             if targets.contains(&md.pretty_name)
                 || targets.contains(&md.get_harness_name_unqualified().to_string())
                 || targets.iter().any(|target| md.pretty_name.contains(*target))
+                || targets.iter().any(|target| is_crate_qualified_name(md, target))
             {
                 result.push(*md);
             } else {
@@ -221,6 +226,16 @@ fn find_proof_harnesses<'a>(
     result
 }
 
+/// Check if the target is the name of the harness prefixed by its crate name, e.g.:
+/// `my_crate::module::harness`. This allow users to select between harnesses with the same name
+/// from different crates.
+pub fn is_crate_qualified_name(harness: &HarnessMetadata, target: &str) -> bool {
+    target
+        .strip_prefix(harness.crate_name.as_str())
+        .and_then(|name| name.strip_prefix("::"))
+        .is_some_and(|name| name == harness.pretty_name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,4 +325,22 @@ fn check_find_proof_harness_with_exact() {
             "module::not_check_three"
         );
     }
+
+    #[test]
+    fn check_find_proof_harness_by_crate_name() {
+        let harnesses = vec![
+            mock_proof_harness("check", None, Some("first"), None),
+            mock_proof_harness("check", None, Some("second"), None),
+        ];
+        let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
+        for exact in [true, false] {
+            let found = find_proof_harnesses(
+                &BTreeSet::from([&"second::check".to_string()]),
+                &ref_harnesses,
+                exact,
+            );
+            assert_eq!(found.len(), 1);
+            assert_eq!(found[0].crate_name, "second");
+        }
+    }
 }
@@ -16,19 +16,35 @@
 //! allows the rest of the driver to handle a function under verification the same way it handle
 //! other harnesses.
 
-use crate::metadata::{from_json, merge_kani_metadata, mock_proof_harness};
+use crate::call_cargo::CargoOutputs;
+use crate::call_goto_cc::link_cache_file;
+use crate::metadata::{
+    from_json, is_crate_qualified_name, merge_kani_metadata, mock_proof_harness,
+};
 use crate::session::KaniSession;
-use crate::util::{crate_name, guess_rlib_name};
-use anyhow::{Context, Result};
+use crate::util::{
+    alter_extension, crate_name, file_digest, guess_rlib_name, thread_pool, warning,
+};
+use anyhow::{bail, Context, Result};
 use kani_metadata::{
     artifact::convert_type, ArtifactType, ArtifactType::*, HarnessMetadata, KaniMetadata,
 };
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{self, BufWriter};
 use std::ops::Deref;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{debug, trace};
 
+/// The name of the file where we store the project manifest after a build.
+const MANIFEST_FILE: &str = "kani-project.json";
+
+/// The name of the file where we store the fingerprint of the last cargo build.
+const BUILD_FINGERPRINT_FILE: &str = "kani-build-fingerprint.json";
+
 /// This structure represent the project information relevant for verification.
 /// A `Project` contains information about all crates under verification, as well as all
 /// artifacts relevant for verification.
@@ -47,6 +63,9 @@ pub struct Project {
     pub outdir: PathBuf,
     /// The collection of artifacts kept as part of this project.
     artifacts: Vec<Artifact>,
+    /// Files that must never be deleted, either because they were provided by the user instead of
+    /// being produced by this build, or because cargo tracks them as its own build outputs.
+    preserved: Vec<PathBuf>,
     /// A flag that indicated whether all artifacts have been merged or not.
     ///
     /// This allow us to provide a consistent behavior for `--function`.
@@ -60,6 +79,29 @@ pub struct Project {
     pub cargo_metadata: Option<cargo_metadata::Metadata>,
     /// For build `keep_going` mode, we collect the targets that we failed to compile.
     pub failed_targets: Option<Vec<String>>,
+    /// The output captured while linking each goto binary, indexed by the goto binary path.
+    link_logs: HashMap<PathBuf, String>,
+    /// The time spent in each phase of the build.
+    pub build_timings: BuildTimings,
+}
+
+/// The time spent in each phase of a project build.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuildTimings {
+    /// Time spent compiling the target crates.
+    pub compile: Duration,
+    /// Time spent linking each goto binary, indexed by the goto binary path.
+    pub link: BTreeMap<PathBuf, Duration>,
+    /// Time spent reading and merging the metadata of the target crates.
+    pub metadata: Duration,
+}
+
+impl BuildTimings {
+    /// The time spent in all phases. Since models are linked in parallel, this may be larger than
+    /// the time it actually took to build the project.
+    pub fn total(&self) -> Duration {
+        self.compile + self.link.values().sum::<Duration>() + self.metadata
+    }
 }
 
 impl Project {
@@ -75,6 +117,47 @@ pub fn get_all_harnesses(&self) -> Vec<&HarnessMetadata> {
             .collect()
     }
 
+    /// Return the harness with the given name, if any. The name is compared against the pretty
+    /// name, the mangled name and the crate qualified name (`<crate>::<pretty name>`) of each
+    /// harness.
+    ///
+    /// If more than one harness has the given name, the first one found is returned.
+    pub fn get_harness(&self, name: &str) -> Option<&HarnessMetadata> {
+        self.get_all_harnesses().into_iter().find(|harness| {
+            harness.pretty_name == name
+                || harness.mangled_name == name
+                || is_crate_qualified_name(harness, name)
+        })
+    }
+
+    /// Return the names of the harnesses that are defined in more than one crate.
+    pub fn duplicate_harness_names(&self) -> Vec<&str> {
+        let mut crates_per_name: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for harness in self.get_all_harnesses() {
+            let crates = crates_per_name.entry(&harness.pretty_name).or_default();
+            if !crates.contains(&harness.crate_name.as_str()) {
+                crates.push(&harness.crate_name);
+            }
+        }
+        crates_per_name
+            .into_iter()
+            .filter_map(|(name, crates)| (crates.len() > 1).then_some(name))
+            .collect()
+    }
+
+    /// Return all harnesses whose pretty name or mangled name match the given glob pattern.
+    pub fn get_harnesses_matching(&self, pattern: &str) -> Result<Vec<&HarnessMetadata>> {
+        let pattern = glob::Pattern::new(pattern)
+            .with_context(|| format!("Invalid harness pattern `{pattern}`"))?;
+        Ok(self
+            .get_all_harnesses()
+            .into_iter()
+            .filter(|harness| {
+                pattern.matches(&harness.pretty_name) || pattern.matches(&harness.mangled_name)
+            })
+            .collect())
+    }
+
     /// Return the matching artifact for the given harness.
     ///
     /// If the harness has information about the goto_file we can use that to find the exact file.
@@ -100,55 +183,455 @@ pub fn get_harness_artifact(
         })
     }
 
+    /// Return all artifacts of the given type in this project.
+    pub fn artifacts_of_type(&self, typ: ArtifactType) -> impl Iterator<Item = &Artifact> {
+        self.artifacts.iter().filter(move |artifact| artifact.has_type(typ))
+    }
+
+    /// Summarize the project information that is relevant for tools that consume Kani's output.
+    pub fn manifest(&self) -> ProjectManifest {
+        let harnesses = self
+            .get_all_harnesses()
+            .into_iter()
+            .map(|harness| HarnessManifest {
+                pretty_name: harness.pretty_name.clone(),
+                crate_name: harness.crate_name.clone(),
+                artifacts: [Goto, SymTabGoto, SymTab, TypeMap, VTableRestriction, PrettyNameMap]
+                    .into_iter()
+                    .filter_map(|typ| {
+                        Some((typ, self.get_harness_artifact(harness, typ)?.path.clone()))
+                    })
+                    .collect(),
+            })
+            .collect();
+        ProjectManifest {
+            outdir: self.outdir.clone(),
+            crates: self.metadata.iter().map(|krate| krate.crate_name.clone()).collect(),
+            harnesses,
+            failed_targets: self.failed_targets.clone(),
+            build_timings: self.build_timings.clone(),
+        }
+    }
+
+    /// Write the project manifest in JSON format to the given path.
+    pub fn write_manifest(&self, path: &Path) -> Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &self.manifest())?;
+        Ok(())
+    }
+
+    /// Delete every file generated for this project, i.e., the artifacts produced by this build
+    /// under `outdir`, the link cache of its goto binaries and its manifest. Files that no longer
+    /// exist are ignored.
+    ///
+    /// Files provided by the user are never deleted. Neither are the outputs of the compiler for
+    /// crates built by cargo, since cargo would still consider them fresh and skip rebuilding
+    /// them in the next run. Only the goto binaries linked from them are deleted.
+    pub fn clean(&self) -> Result<()> {
+        let generated: Vec<_> = self
+            .artifacts
+            .iter()
+            .filter(|artifact| {
+                artifact.starts_with(&self.outdir) && !self.preserved.contains(&artifact.path)
+            })
+            .collect();
+        let link_caches = generated
+            .iter()
+            .filter(|artifact| artifact.has_type(Goto))
+            .map(|goto| link_cache_file(goto))
+            .collect::<Vec<_>>();
+        let files = generated
+            .iter()
+            .map(|artifact| artifact.path.clone())
+            .chain(link_caches)
+            .chain([self.outdir.join(MANIFEST_FILE)]);
+        for file in files {
+            match std::fs::remove_file(&file) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                    return Err(err)
+                        .with_context(|| format!("Failed to remove {}", file.display()));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the combined size in bytes of all artifacts of the given type.
+    pub fn total_artifact_size(&self, typ: ArtifactType) -> io::Result<u64> {
+        self.artifacts_of_type(typ).map(Artifact::size_bytes).sum()
+    }
+
+    /// Summarize the targets that failed to build in JSON format, e.g.:
+    /// `{"count":2,"targets":["lib","bin"]}`.
+    ///
+    /// Returns `None` if the project wasn't built in `keep_going` mode.
+    pub fn failed_targets_report(&self) -> Option<String> {
+        let targets = self.failed_targets.as_ref()?;
+        let report = FailedTargetsReport { count: targets.len(), targets };
+        Some(serde_json::to_string(&report).unwrap())
+    }
+
+    /// Return the output captured while linking the goto binary of the given harness, if any.
+    pub fn get_harness_link_log(&self, harness: &HarnessMetadata) -> Option<&str> {
+        let goto = self.get_harness_artifact(harness, Goto)?;
+        self.link_logs.get(&goto.path).map(String::as_str)
+    }
+
+    /// Record the digest of the goto model of every harness in this project to the given lock file.
+    ///
+    /// This should only be invoked after all harnesses have been successfully verified, so a
+    /// later run can use [Project::check_verified_lock] to detect models that changed since.
+    ///
+    /// Harnesses are identified by their crate qualified name (`<crate>::<pretty name>`), since
+    /// harnesses in different crates may have the same name.
+    pub fn write_verified_lock(&self, path: &Path) -> Result<()> {
+        let digests = self.harness_digests()?;
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &digests)?;
+        Ok(())
+    }
+
+    /// Check that the goto model of every harness in this project matches the digest recorded in
+    /// the given lock file. Harnesses that are missing from the lock file are reported as changed.
+    pub fn check_verified_lock(&self, path: &Path) -> Result<()> {
+        let expected: BTreeMap<String, String> = from_json(path)
+            .with_context(|| format!("Failed to read lock file `{}`", path.display()))?;
+        let changed = self
+            .harness_digests()?
+            .into_iter()
+            .filter_map(|(name, digest)| (expected.get(&name) != Some(&digest)).then_some(name))
+            .collect::<Vec<_>>();
+        if !changed.is_empty() {
+            bail!(
+                "The goto model of the following harness(es) changed since they were last \
+                verified:\n`{}`\nPlease verify them again to update `{}`.",
+                changed.join("`, `"),
+                path.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Compute the digest of the goto model for each harness, indexed by the crate qualified name
+    /// of the harness.
+    ///
+    /// We use the model generated by the compiler when there is one. Projects built from goto
+    /// binaries, e.g. with `--goto-metadata`, only have the goto binary.
+    fn harness_digests(&self) -> Result<BTreeMap<String, String>> {
+        self.get_all_harnesses()
+            .into_iter()
+            .map(|harness| {
+                let model = self
+                    .get_harness_artifact(harness, SymTabGoto)
+                    .or_else(|| self.get_harness_artifact(harness, Goto))
+                    .with_context(|| {
+                        format!(
+                            "Failed to find the goto model for harness `{}`",
+                            harness.pretty_name
+                        )
+                    })?;
+                let name = format!("{}::{}", harness.crate_name, harness.pretty_name);
+                Ok((name, file_digest(model)?))
+            })
+            .collect()
+    }
+
+    /// Combine two projects that were built independently into one project that includes the
+    /// crates and artifacts of both.
+    ///
+    /// The output directory of `self` is kept, as well as its cargo metadata if there is any.
+    /// This fails if both projects include the same crate, or if any of them has its artifacts
+    /// merged, since the combined project would end up with more than one artifact of each type.
+    pub fn merge(mut self, other: Project) -> Result<Project> {
+        if self.merged_artifacts || other.merged_artifacts {
+            bail!("Cannot merge projects whose artifacts have been linked together");
+        }
+        if let Some(krate) = other.metadata.iter().find(|other_krate| {
+            self.metadata.iter().any(|krate| krate.crate_name == other_krate.crate_name)
+        }) {
+            bail!("crate `{}` was included more than once", krate.crate_name);
+        }
+        self.metadata.extend(other.metadata);
+        self.artifacts.extend(other.artifacts);
+        self.preserved.extend(other.preserved);
+        self.cargo_metadata = self.cargo_metadata.or(other.cargo_metadata);
+        self.failed_targets = match (self.failed_targets, other.failed_targets) {
+            (Some(mut failed_targets), Some(other_targets)) => {
+                failed_targets.extend(other_targets);
+                Some(failed_targets)
+            }
+            (failed_targets, other_targets) => failed_targets.or(other_targets),
+        };
+        self.link_logs.extend(other.link_logs);
+        self.build_timings.compile += other.build_timings.compile;
+        self.build_timings.metadata += other.build_timings.metadata;
+        self.build_timings.link.extend(other.build_timings.link);
+        Ok(self)
+    }
+
+    /// Build a project from goto binaries that were generated outside of Kani, e.g., by another
+    /// tool, given the metadata of the harnesses they contain. Nothing is compiled or linked.
+    ///
+    /// If there is a single goto binary, every harness is verified against it. Otherwise, the
+    /// goto binary of each harness is found from the goto model recorded in its metadata, and
+    /// every harness must resolve to one of the given goto binaries.
+    pub fn from_goto_files(paths: &[PathBuf], metadata: KaniMetadata) -> Result<Project> {
+        if paths.is_empty() {
+            bail!("Expected at least one goto file");
+        }
+        let artifacts =
+            paths.iter().map(|path| Artifact::try_new(path, Goto)).collect::<Result<Vec<_>>>()?;
+        let project = Project {
+            outdir: artifacts[0].parent().unwrap().to_path_buf(),
+            metadata: vec![metadata],
+            merged_artifacts: artifacts.len() == 1,
+            preserved: artifacts.iter().map(|artifact| artifact.path.clone()).collect(),
+            artifacts,
+            ..Default::default()
+        };
+        if !project.merged_artifacts {
+            let unmatched: Vec<_> = project
+                .get_all_harnesses()
+                .into_iter()
+                .filter(|harness| {
+                    let goto = harness.goto_file.as_ref().and_then(|goto_file| {
+                        convert_type(goto_file, SymTabGoto, Goto).canonicalize().ok()
+                    });
+                    !goto.map_or(false, |goto| project.artifacts.iter().any(|a| a.path == goto))
+                })
+                .map(|harness| harness.pretty_name.as_str())
+                .collect();
+            if !unmatched.is_empty() {
+                bail!(
+                    "The following harness(es) do not match any of the provided goto files: `{}`",
+                    unmatched.join("`, `")
+                );
+            }
+        }
+        Ok(project)
+    }
+
+    /// Build a project that includes crates built by cargo as well as crates that were compiled
+    /// standalone, i.e., without cargo.
+    ///
+    /// The cargo metadata of the project only covers the crates built by cargo. All outputs are
+    /// expected to be in their own model files, so the artifacts are never merged.
+    pub fn from_mixed(
+        cargo_outputs: CargoOutputs,
+        standalone_outputs: &[Artifact],
+        session: &KaniSession,
+    ) -> Result<Self> {
+        let outdir = cargo_outputs.outdir.clone();
+        let mut metadata_time = Duration::ZERO;
+        let (cargo_metadata, standalone_metadata) = timed(&mut metadata_time, || -> Result<_> {
+            Ok((read_metadata(&cargo_outputs.metadata)?, read_metadata(standalone_outputs)?))
+        })?;
+        let cargo_project = Project::try_new(
+            session,
+            outdir.clone(),
+            cargo_metadata,
+            Some(cargo_outputs.cargo_metadata),
+            cargo_outputs.failed_targets,
+            true,
+        )?;
+        let standalone_project =
+            Project::try_new(session, outdir, standalone_metadata, None, None, true)?;
+        let mut project = cargo_project
+            .merge(standalone_project)
+            .context("Crates built by cargo cannot also be provided as standalone outputs")?;
+        // The crates were compiled either by cargo or by the user, so only the goto binaries that
+        // we linked belong to this build.
+        project.preserved = project
+            .artifacts
+            .iter()
+            .filter(|artifact| !artifact.has_type(Goto))
+            .chain(standalone_outputs)
+            .map(|artifact| artifact.path.clone())
+            .collect();
+        project.build_timings.metadata = metadata_time;
+        project.warn_duplicate_harnesses(session);
+        Ok(project)
+    }
+
     /// Try to build a new project from the build result metadata.
     ///
     /// This method will parse the metadata in order to gather all artifacts generated by the
     /// compiler.
+    ///
+    /// If `link_cache` is set, goto binaries that were already linked from the same models by a
+    /// previous run are reused. This should only be set if the outputs are kept across runs.
     fn try_new(
         session: &KaniSession,
         outdir: PathBuf,
-        metadata: Vec<KaniMetadata>,
+        mut metadata: Vec<KaniMetadata>,
         cargo_metadata: Option<cargo_metadata::Metadata>,
         failed_targets: Option<Vec<String>>,
+        link_cache: bool,
     ) -> Result<Self> {
-        // For each harness (test or proof) from each metadata, read the path for the goto
-        // SymTabGoto file. Use that path to find all the other artifacts.
-        let mut artifacts = vec![];
-        for crate_metadata in &metadata {
-            for harness_metadata in
-                crate_metadata.test_harnesses.iter().chain(crate_metadata.proof_harnesses.iter())
-            {
-                let symtab_out = Artifact::try_new(
-                    harness_metadata.goto_file.as_ref().expect("Expected a model file"),
-                    SymTabGoto,
-                )?;
-                let goto_path = convert_type(&symtab_out.path, symtab_out.typ, Goto);
-
-                // Link
-                session.link_goto_binary(&[symtab_out.to_path_buf()], &goto_path)?;
-                let goto = Artifact::try_new(&goto_path, Goto)?;
-
-                // All other harness artifacts that may have been generated as part of the build.
-                artifacts.extend(
-                    [SymTab, TypeMap, VTableRestriction, PrettyNameMap].iter().filter_map(|typ| {
-                        let artifact = Artifact::try_from(&symtab_out, *typ).ok()?;
-                        Some(artifact)
-                    }),
-                );
-                artifacts.push(symtab_out);
-                artifacts.push(goto);
+        // In `keep_going` mode, we still want to verify the harnesses that were built correctly.
+        if failed_targets.is_some() {
+            for harness in remove_harnesses_without_model(&mut metadata) {
+                if !session.args.common_args.quiet {
+                    warning(&format!(
+                        "Skipping harness `{}` since its goto model could not be found.",
+                        harness.pretty_name
+                    ));
+                }
             }
         }
 
+        // Harnesses from the same crate may share the same model, so make sure we only link each
+        // model once.
+        let models = unique_models(&metadata)?;
+
+        // Linking each model is independent, so we link them in parallel. Note that `collect`
+        // preserves the order of the models, so the artifacts are the same as a serial build.
+        let pool = thread_pool(session.args.link_jobs())?;
+        let links = pool.install(|| {
+            models
+                .into_par_iter()
+                .map(|symtab_out| -> Result<(Vec<Artifact>, (PathBuf, String, Duration))> {
+                    let goto_path = convert_type(&symtab_out.path, symtab_out.typ, Goto);
+                    let goto = Artifact::expected(&goto_path, Goto)?;
+
+                    // Link
+                    let mut link_time = Duration::ZERO;
+                    let link_log = timed(&mut link_time, || {
+                        let inputs = [symtab_out.to_path_buf()];
+                        if link_cache {
+                            session.link_goto_binary_cached(&inputs, &goto)
+                        } else {
+                            session.link_goto_binary(&inputs, &goto)
+                        }
+                    })?;
+                    let link_log = (goto.path.clone(), link_log, link_time);
+
+                    // All other harness artifacts that may have been generated as part of the
+                    // build.
+                    let mut harness_artifacts = [SymTab, TypeMap, VTableRestriction, PrettyNameMap]
+                        .iter()
+                        .filter_map(|typ| Artifact::try_from(&symtab_out, *typ).ok())
+                        .collect::<Vec<_>>();
+                    harness_artifacts.push(symtab_out);
+                    harness_artifacts.push(goto);
+                    Ok((harness_artifacts, link_log))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        let (artifacts, links): (Vec<_>, Vec<_>) = links.into_iter().unzip();
+        let artifacts = artifacts.into_iter().flatten().collect();
+        let mut link_logs = HashMap::new();
+        let mut build_timings = BuildTimings::default();
+        for (goto, link_log, link_time) in links {
+            build_timings.link.insert(goto.clone(), link_time);
+            link_logs.insert(goto, link_log);
+        }
+
         Ok(Project {
             outdir,
             metadata,
             artifacts,
+            preserved: vec![],
             merged_artifacts: false,
             cargo_metadata,
             failed_targets,
+            link_logs,
+            build_timings,
         })
     }
+
+    /// Warn the user if there are harnesses with the same name in different crates.
+    fn warn_duplicate_harnesses(&self, session: &KaniSession) {
+        let duplicates = self.duplicate_harness_names();
+        if !duplicates.is_empty() && !session.args.common_args.quiet {
+            warning(&format!(
+                "Found harnesses with the same name in different crates: `{}`. Use the crate name \
+                as a prefix to select one of them, e.g.: `--harness <crate_name>::{}`.",
+                duplicates.join("`, `"),
+                duplicates[0]
+            ));
+        }
+    }
+}
+
+/// Collect the model (SymTabGoto) file of each harness (test or proof) from each metadata.
+/// Each model is only included once, even if it is shared by multiple harnesses.
+fn unique_models(metadata: &[KaniMetadata]) -> Result<Vec<Artifact>> {
+    let mut models = vec![];
+    for harness_metadata in metadata.iter().flat_map(|crate_metadata| {
+        crate_metadata.test_harnesses.iter().chain(crate_metadata.proof_harnesses.iter())
+    }) {
+        let goto_file = harness_metadata.goto_file.as_ref().with_context(|| {
+            format!(
+                "harness `{}` has no goto model. The compiler did not generate a model file for \
+                `{}`, which may indicate that its compilation failed.",
+                harness_metadata.pretty_name, harness_metadata.mangled_name
+            )
+        })?;
+        let symtab_out = Artifact::try_new(goto_file, SymTabGoto)?;
+        if !models.contains(&symtab_out) {
+            models.push(symtab_out);
+        }
+    }
+    Ok(models)
+}
+
+/// Remove every harness whose model (SymTabGoto) file is missing from the given metadata, and
+/// return the harnesses that were removed.
+fn remove_harnesses_without_model(metadata: &mut [KaniMetadata]) -> Vec<HarnessMetadata> {
+    let has_model = |harness: &HarnessMetadata| {
+        harness.goto_file.as_ref().is_some_and(|goto_file| goto_file.exists())
+    };
+    let mut removed = vec![];
+    for crate_metadata in metadata {
+        for harnesses in [&mut crate_metadata.proof_harnesses, &mut crate_metadata.test_harnesses] {
+            let (kept, missing) = std::mem::take(harnesses).into_iter().partition(has_model);
+            *harnesses = kept;
+            removed.extend(missing);
+        }
+    }
+    removed
+}
+
+/// Read the metadata of each of the given crates.
+fn read_metadata(metadata_files: &[Artifact]) -> Result<Vec<KaniMetadata>> {
+    // For the MIR Linker we know there is only one metadata per crate. Use that in our favor.
+    metadata_files.iter().map(|md_file| from_json::<KaniMetadata>(md_file)).collect()
+}
+
+/// The targets that failed to build in `keep_going` mode.
+#[derive(Serialize)]
+struct FailedTargetsReport<'a> {
+    count: usize,
+    targets: &'a [String],
+}
+
+/// A summary of a `Project` which is stored in the project directory after a build, so other
+/// tools can consume a single file to find all the project artifacts.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    /// The directory where all outputs were directed to.
+    pub outdir: PathBuf,
+    /// The name of each target crate.
+    pub crates: Vec<String>,
+    /// The harnesses (test and proof) of all target crates.
+    pub harnesses: Vec<HarnessManifest>,
+    /// For build `keep_going` mode, the targets that we failed to compile.
+    pub failed_targets: Option<Vec<String>>,
+    /// The time spent in each phase of the build.
+    pub build_timings: BuildTimings,
+}
+
+/// A summary of one harness and the artifacts used to verify it.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct HarnessManifest {
+    /// The fully qualified name of the harness.
+    pub pretty_name: String,
+    /// The crate where the harness was declared.
+    pub crate_name: String,
+    /// The path of each artifact of this harness indexed by their type.
+    pub artifacts: BTreeMap<ArtifactType, PathBuf>,
 }
 
 /// Information about a build artifact.
@@ -175,11 +658,52 @@ fn deref(&self) -> &Self::Target {
 
 impl Artifact {
     /// Create a new artifact if the given path exists.
+    ///
+    /// The error distinguishes a missing file from a dangling symbolic link and from a file that
+    /// cannot be accessed, and it reports the path as given, i.e., before resolving any link.
     pub fn try_new(path: &Path, typ: ArtifactType) -> Result<Self> {
-        Ok(Artifact {
-            path: path.canonicalize().context(format!("Failed to process {}", path.display()))?,
-            typ,
-        })
+        let canonical = path.canonicalize().map_err(|err| {
+            let reason = match err.kind() {
+                io::ErrorKind::NotFound if path.symlink_metadata().is_ok() => {
+                    match std::fs::read_link(path) {
+                        Ok(target) => {
+                            format!("symbolic link target `{}` does not exist", target.display())
+                        }
+                        Err(_) => "symbolic link target does not exist".to_string(),
+                    }
+                }
+                io::ErrorKind::NotFound => "file does not exist".to_string(),
+                io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+                _ => err.to_string(),
+            };
+            anyhow::Error::new(err)
+                .context(format!("Failed to process {}: {reason}", path.display()))
+        })?;
+        Ok(Artifact { path: canonical, typ })
+    }
+
+    /// Create a new artifact for a file that may not exist yet, such as an output of the linker.
+    ///
+    /// The path is converted to an absolute path and normalized lexically, i.e., symbolic links
+    /// are not resolved. Thus, the given path should be derived from a canonical path, such as
+    /// the project `outdir`, so the artifact matches artifacts created via [Artifact::try_new].
+    pub fn expected(path: &Path, typ: ArtifactType) -> Result<Self> {
+        let mut normalized = PathBuf::new();
+        for component in std::env::current_dir()?.join(path).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                _ => normalized.push(component),
+            }
+        }
+        Ok(Artifact { path: normalized, typ })
+    }
+
+    /// Return the size of this artifact in bytes.
+    pub fn size_bytes(&self) -> io::Result<u64> {
+        Ok(self.path.metadata()?.len())
     }
 
     /// Check if this artifact has the given type.
@@ -198,6 +722,32 @@ pub fn try_from(artifact: &Artifact, typ: ArtifactType) -> Result<Self> {
     }
 }
 
+/// Create the given output directory if it doesn't exist yet, and check that we can write to it.
+/// Return the canonical form of the directory.
+///
+/// This should be invoked before anything is written to the directory, so we fail early.
+pub fn ensure_outdir(outdir: &Path) -> Result<PathBuf> {
+    // This is a no-op if the directory already exists.
+    std::fs::create_dir_all(outdir)
+        .with_context(|| format!("cannot create output directory {}", outdir.display()))?;
+    let canonical = outdir.canonicalize()?;
+    // The directory permissions don't tell us whether we can write to it, e.g., for privileged
+    // users or read-only file systems, so we try to create a file instead.
+    let probe = canonical.join(format!(".kani-write-probe-{:08x}", rand::random::<u32>()));
+    File::create(&probe)
+        .and_then(|_| std::fs::remove_file(&probe))
+        .with_context(|| format!("cannot write to output directory {}", outdir.display()))?;
+    Ok(canonical)
+}
+
+/// Run the given function and add the time it took to `duration`.
+fn timed<T>(duration: &mut Duration, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    *duration += start.elapsed();
+    result
+}
+
 /// Store the KaniMetadata into a file.
 fn dump_metadata(metadata: &KaniMetadata, path: &Path) {
     let out_file = File::create(path).unwrap();
@@ -205,13 +755,35 @@ fn dump_metadata(metadata: &KaniMetadata, path: &Path) {
     serde_json::to_writer_pretty(writer, &metadata).unwrap();
 }
 
+/// Collect the harnesses of a `cargo` project without linking its goto models.
+///
+/// This still compiles the project, but it is much cheaper than [cargo_project] for tools that
+/// only need to enumerate the harnesses, since no goto binary is generated.
+pub fn cargo_harnesses(session: &KaniSession, keep_going: bool) -> Result<Vec<HarnessMetadata>> {
+    let outputs = rebuild_if_stale(session, keep_going)?;
+    let standalone_outputs = session
+        .args
+        .standalone_metadata
+        .iter()
+        .map(|md_file| Artifact::try_new(md_file, Metadata))
+        .collect::<Result<Vec<_>>>()?;
+    let harnesses = read_metadata(&outputs.metadata)?
+        .into_iter()
+        .chain(read_metadata(&standalone_outputs)?)
+        .flat_map(|krate| krate.proof_harnesses.into_iter().chain(krate.test_harnesses))
+        .collect();
+    Ok(harnesses)
+}
+
 /// Generate a project using `cargo`.
 /// Accept a boolean to build as many targets as possible. The number of failures in that case can
 /// be collected from the project.
 pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project> {
-    let outputs = session.cargo_build(keep_going)?;
-    let outdir = outputs.outdir.canonicalize()?;
-    if session.args.function.is_some() {
+    let mut compile_time = Duration::ZERO;
+    let outputs = timed(&mut compile_time, || rebuild_if_stale(session, keep_going))?;
+    let outdir = outputs.outdir.clone();
+    let mut project = if !session.args.function.is_empty() {
+        let mut build_timings = BuildTimings::default();
         let mut artifacts = vec![];
         // For the `--function` support, we still use a glob to link everything.
         // Yes, this is broken, but it has been broken for quite some time. :(
@@ -219,21 +791,26 @@ pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project>
         // https://github.com/model-checking/kani/issues/2129
         let joined_name = "cbmc-linked";
         let base_name = outdir.join(joined_name);
-        let goto = base_name.with_extension(Goto);
+        let goto_artifact = Artifact::expected(&base_name.with_extension(Goto), Goto)?;
         let all_gotos = outputs
             .metadata
             .iter()
             .map(|artifact| convert_type(&artifact, Metadata, SymTabGoto))
             .collect::<Vec<_>>();
-        session.link_goto_binary(&all_gotos, &goto)?;
-        let goto_artifact = Artifact::try_new(&goto, Goto)?;
+        let mut link_time = Duration::ZERO;
+        let link_log =
+            timed(&mut link_time, || session.link_goto_binary(&all_gotos, &goto_artifact))?;
+        let link_logs = HashMap::from([(goto_artifact.path.clone(), link_log)]);
+        build_timings.link.insert(goto_artifact.path.clone(), link_time);
 
         // Merge metadata files.
-        let per_crate: Vec<_> =
-            outputs.metadata.iter().filter_map(|f| from_json::<KaniMetadata>(f).ok()).collect();
-        let merged_metadata = merge_kani_metadata(per_crate);
+        let merged_metadata = timed(&mut build_timings.metadata, || {
+            let per_crate: Vec<_> =
+                outputs.metadata.iter().filter_map(|f| from_json::<KaniMetadata>(f).ok()).collect();
+            merge_kani_metadata(per_crate)
+        });
         let metadata = metadata_with_function(
-            session,
+            &session.args.function,
             joined_name,
             merged_metadata,
             goto_artifact.with_extension(SymTabGoto),
@@ -243,120 +820,445 @@ pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project>
         artifacts.push(goto_artifact);
         artifacts.push(Artifact::try_new(&metadata_file, Metadata)?);
 
-        Ok(Project {
+        Project {
             outdir,
             artifacts,
             metadata: vec![metadata],
+            preserved: vec![],
             merged_artifacts: true,
             cargo_metadata: Some(outputs.cargo_metadata),
             failed_targets: outputs.failed_targets,
-        })
+            link_logs,
+            build_timings,
+        }
     } else {
-        // For the MIR Linker we know there is only one metadata per crate. Use that in our favor.
-        let metadata = outputs
-            .metadata
+        let standalone_outputs = session
+            .args
+            .standalone_metadata
             .iter()
-            .map(|md_file| from_json(md_file))
+            .map(|md_file| Artifact::try_new(md_file, Metadata))
             .collect::<Result<Vec<_>>>()?;
-        Project::try_new(
-            session,
-            outdir,
-            metadata,
-            Some(outputs.cargo_metadata),
-            outputs.failed_targets,
-        )
+        Project::from_mixed(outputs, &standalone_outputs, session)?
+    };
+    project.build_timings.compile = compile_time;
+    project.write_manifest(&project.outdir.join(MANIFEST_FILE))?;
+    Ok(project)
+}
+
+/// Build a `cargo` project.
+///
+/// With `--reuse-unchanged-build`, we skip invoking cargo and reuse the outputs of the previous
+/// build if neither its arguments nor the sources of the packages being built changed since, and
+/// its outputs still exist.
+fn rebuild_if_stale(session: &KaniSession, keep_going: bool) -> Result<CargoOutputs> {
+    let plan = session.cargo_build_plan(keep_going)?;
+    if !session.args.reuse_unchanged_build {
+        return session.cargo_build(plan);
+    }
+    let fingerprint_file = plan.target_dir.join(BUILD_FINGERPRINT_FILE);
+    let previous = from_json::<BuildFingerprint>(&fingerprint_file).ok();
+    let args = plan.args_key();
+    let previous_sources = previous.as_ref().map(|previous| &previous.sources);
+    let sources = source_digests(
+        &plan.packages(),
+        &plan.metadata,
+        &plan.target_dir,
+        previous_sources.unwrap_or(&BTreeMap::new()),
+    )?;
+    if let Some(previous) = previous
+        && previous.is_fresh(&args, &sources)
+    {
+        debug!(?fingerprint_file, "reuse unchanged build");
+        return Ok(CargoOutputs {
+            outdir: plan.outdir,
+            metadata: previous
+                .metadata
+                .iter()
+                .map(|md_file| Artifact::try_new(md_file, Metadata))
+                .collect::<Result<_>>()?,
+            cargo_metadata: plan.metadata,
+            failed_targets: previous.failed_targets,
+        });
+    }
+
+    let outputs = session.cargo_build(plan)?;
+    let fingerprint = BuildFingerprint {
+        args,
+        sources,
+        metadata: outputs.metadata.iter().map(|artifact| artifact.path.clone()).collect(),
+        failed_targets: outputs.failed_targets.clone(),
+    };
+    fingerprint.write(&fingerprint_file)?;
+    Ok(outputs)
+}
+
+/// Information stored after a cargo build, which allow us to skip the next build if nothing
+/// changed.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BuildFingerprint {
+    /// The key of the arguments used in the build.
+    args: String,
+    /// The digest of each source file of the packages that were built.
+    sources: BTreeMap<PathBuf, SourceDigest>,
+    /// The kani-metadata.json files written by the build.
+    metadata: Vec<PathBuf>,
+    /// For build `keep_going` mode, the targets that we failed to compile.
+    failed_targets: Option<Vec<String>>,
+}
+
+impl BuildFingerprint {
+    /// Check if this fingerprint was created with the same arguments and the same content for
+    /// every source file, and all its outputs still exist. This includes the goto files listed in
+    /// each metadata file.
+    fn is_fresh(&self, args: &str, sources: &BTreeMap<PathBuf, SourceDigest>) -> bool {
+        self.args == args
+            && self.sources.len() == sources.len()
+            && self.sources.iter().zip(sources).all(|((path, digest), (other_path, other))| {
+                path == other_path && digest.digest == other.digest
+            })
+            && self.metadata.iter().all(|md_file| outputs_exist(md_file))
+    }
+
+    fn write(&self, fingerprint_file: &Path) -> Result<()> {
+        let writer = BufWriter::new(File::create(fingerprint_file)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+}
+
+/// The digest of a source file, as well as the size and modification time of the file when the
+/// digest was computed, so we only compute it again for files that may have changed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SourceDigest {
+    len: u64,
+    modified: SystemTime,
+    digest: String,
+}
+
+/// Check that the given metadata file and every goto file it refers to exist.
+fn outputs_exist(md_file: &Path) -> bool {
+    let Ok(metadata) = from_json::<KaniMetadata>(md_file) else {
+        return false;
+    };
+    metadata
+        .proof_harnesses
+        .iter()
+        .chain(&metadata.test_harnesses)
+        .filter_map(|harness| harness.goto_file.as_ref())
+        .all(|goto_file| goto_file.exists())
+}
+
+/// Compute the digest of every source file that the given packages may depend on, as well as the
+/// workspace `Cargo.toml` and `Cargo.lock`, and the cargo configuration files that apply to the
+/// workspace.
+///
+/// We walk the dependency closure of the packages using the `resolve` graph from cargo metadata.
+/// Registry and git dependencies are pinned by `Cargo.lock`, while for local packages (path and
+/// workspace dependencies) we hash every file in the package directory, since build scripts and
+/// `include!` may read files that are not Rust sources. Directories of other local packages are
+/// skipped, since they are only relevant if they are part of the closure.
+///
+/// The digest recorded in `previous` is reused for files whose size and modification time didn't
+/// change.
+fn source_digests(
+    packages: &[&cargo_metadata::Package],
+    metadata: &cargo_metadata::Metadata,
+    target_dir: &Path,
+    previous: &BTreeMap<PathBuf, SourceDigest>,
+) -> Result<BTreeMap<PathBuf, SourceDigest>> {
+    let workspace_root = metadata.workspace_root.as_std_path();
+    let mut sources = vec![workspace_root.join("Cargo.toml"), workspace_root.join("Cargo.lock")];
+    // Cargo reads the configuration files in the directory where it is invoked and all its
+    // ancestors.
+    let cwd = std::env::current_dir()?;
+    let config_dirs = workspace_root.ancestors().chain(cwd.ancestors());
+    for dir in config_dirs {
+        sources.push(dir.join(".cargo/config.toml"));
+        sources.push(dir.join(".cargo/config"));
+    }
+
+    let is_local = |package: &&cargo_metadata::Package| package.source.is_none();
+    let package_dir =
+        |package: &cargo_metadata::Package| package.manifest_path.parent().unwrap().to_owned();
+    let mut skip_dirs = vec![metadata.target_directory.clone().into(), target_dir.to_path_buf()];
+    skip_dirs.extend(metadata.packages.iter().filter(is_local).map(|p| package_dir(p).into()));
+    let mut visited = HashSet::new();
+    for package in dependency_closure(packages, metadata).into_iter().filter(is_local) {
+        let dir = package_dir(package);
+        collect_source_files(dir.as_std_path(), &skip_dirs, &mut visited, &mut sources)?;
+    }
+    sources.sort();
+    sources.dedup();
+    sources
+        .into_iter()
+        .filter_map(|source| {
+            let file_metadata = std::fs::metadata(&source).ok()?;
+            Some((source, file_metadata))
+        })
+        .map(|(source, file_metadata)| {
+            let len = file_metadata.len();
+            let modified = file_metadata.modified()?;
+            let digest = match previous.get(&source) {
+                Some(digest) if digest.len == len && digest.modified == modified => {
+                    digest.digest.clone()
+                }
+                _ => file_digest(&source)?,
+            };
+            Ok((source, SourceDigest { len, modified, digest }))
+        })
+        .collect()
+}
+
+/// Collect the given packages and all their transitive dependencies.
+fn dependency_closure<'a>(
+    packages: &[&'a cargo_metadata::Package],
+    metadata: &'a cargo_metadata::Metadata,
+) -> Vec<&'a cargo_metadata::Package> {
+    let Some(resolve) = &metadata.resolve else {
+        return packages.to_vec();
+    };
+    let mut visited: Vec<&cargo_metadata::PackageId> = vec![];
+    let mut worklist: Vec<_> = packages.iter().map(|package| &package.id).collect();
+    while let Some(id) = worklist.pop() {
+        if visited.contains(&id) {
+            continue;
+        }
+        visited.push(id);
+        if let Some(node) = resolve.nodes.iter().find(|node| node.id == *id) {
+            worklist.extend(node.dependencies.iter());
+        }
+    }
+    metadata.packages.iter().filter(|package| visited.contains(&&package.id)).collect()
+}
+
+/// Recursively collect the files under `dir`, skipping hidden directories as well as the
+/// directories in `skip_dirs`.
+///
+/// Symbolic links to directories are followed, so we keep the canonical path of every directory
+/// in `visited` to avoid walking the same directory twice, or forever if the links form a cycle.
+fn collect_source_files(
+    dir: &Path,
+    skip_dirs: &[PathBuf],
+    visited: &mut HashSet<PathBuf>,
+    sources: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let canonical_dir =
+        dir.canonicalize().with_context(|| format!("Failed to read {}", dir.display()))?;
+    if !visited.insert(canonical_dir) {
+        return Ok(());
+    }
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            let is_hidden = path.file_name().unwrap().to_string_lossy().starts_with('.');
+            if !is_hidden && !skip_dirs.contains(&path) {
+                collect_source_files(&path, skip_dirs, visited, sources)?;
+            }
+        } else {
+            sources.push(path);
+        }
     }
+    Ok(())
+}
+
+/// Generate a project directly using `kani-compiler` on each input file, where each file is
+/// compiled as a separate crate.
+pub fn standalone_project(inputs: &[PathBuf], session: &KaniSession) -> Result<Project> {
+    StandaloneProjectBuilder::try_new(inputs, session)?.build()
+}
+
+/// Generate a project from a goto binary that was built outside of Kani, given the metadata file
+/// of its harnesses.
+///
+/// The goto binary is copied to the output directory of the session, so every file generated
+/// during verification is written there instead of next to the input.
+pub fn goto_project(input: &Path, md_file: &Path, session: &KaniSession) -> Result<Project> {
+    let metadata = from_json(md_file)?;
+    let outdir = standalone_outdir(input, session)?;
+    let goto = alter_extension(&outdir.join(input.file_name().unwrap()), "kani.out");
+    session.record_temporary_file(&goto);
+    std::fs::copy(input, &goto)
+        .with_context(|| format!("Failed to copy {} to {}", input.display(), goto.display()))?;
+    Project::from_goto_files(&[goto], metadata)
 }
 
-/// Generate a project directly using `kani-compiler` on a single crate.
-pub fn standalone_project(input: &Path, session: &KaniSession) -> Result<Project> {
-    StandaloneProjectBuilder::try_new(input, session)?.build()
+/// Select the output directory for a project that is not built by cargo, and ensure it exists and
+/// is in its canonical form.
+///
+/// Unless the user asked to keep the outputs, we use a new temporary directory that is deleted
+/// at the end of the session.
+fn standalone_outdir(first_input: &Path, session: &KaniSession) -> Result<PathBuf> {
+    let args = &session.args;
+    if let Some(target_dir) = &args.target_dir {
+        ensure_outdir(target_dir)
+    } else if args.keep_temps || args.gen_c || args.visualize {
+        Ok(first_input.canonicalize()?.parent().unwrap().to_path_buf())
+    } else {
+        let hash: u32 = rand::random();
+        let outdir =
+            std::env::temp_dir().join(format!("kani_{}_{hash:08x}", crate_name(first_input)));
+        let canonical_outdir = ensure_outdir(&outdir)?;
+        // The directory is deleted with all its content, including any partial outputs of a failed
+        // build.
+        session.record_temporary_dir(&canonical_outdir);
+        Ok(canonical_outdir)
+    }
 }
 
 /// Builder for a standalone project.
 struct StandaloneProjectBuilder<'a> {
     /// The directory where all outputs should be directed to.
     outdir: PathBuf,
-    /// The metadata file for the target crate.
+    /// The crates to be compiled.
+    crates: Vec<StandaloneCrate>,
+    /// The Kani session.
+    session: &'a KaniSession,
+}
+
+/// A crate that is compiled directly from one input file.
+struct StandaloneCrate {
+    /// The metadata file for the crate.
     metadata: Artifact,
     /// The input file.
     input: PathBuf,
     /// The crate name.
     crate_name: String,
-    /// The Kani session.
-    session: &'a KaniSession,
 }
 
 impl<'a> StandaloneProjectBuilder<'a> {
-    /// Create a `StandaloneProjectBuilder` from the given input and session.
+    /// Create a `StandaloneProjectBuilder` from the given inputs and session.
     /// This will perform a few validations before the build.
-    fn try_new(input: &Path, session: &'a KaniSession) -> Result<Self> {
-        // Ensure the directory exist and it's in its canonical form.
-        let outdir = if let Some(target_dir) = &session.args.target_dir {
-            std::fs::create_dir_all(target_dir)?; // This is a no-op if directory exists.
-            target_dir.canonicalize()?
-        } else {
-            input.canonicalize().unwrap().parent().unwrap().to_path_buf()
-        };
-        let crate_name = crate_name(&input);
-        let metadata = standalone_artifact(&outdir, &crate_name, Metadata);
-        Ok(StandaloneProjectBuilder {
-            outdir,
-            metadata,
-            input: input.to_path_buf(),
-            crate_name,
-            session,
-        })
+    ///
+    /// If no target directory was provided, the outputs are directed to a new temporary
+    /// directory, so we don't pollute the user's source tree. The only exception is when the user
+    /// asked for outputs to be kept, in which case they are directed to the directory of the
+    /// first input.
+    fn try_new(inputs: &[PathBuf], session: &'a KaniSession) -> Result<Self> {
+        let Some(first_input) = inputs.first() else { bail!("No input file was provided") };
+        let outdir = standalone_outdir(first_input, session)?;
+        let mut crates: Vec<StandaloneCrate> = vec![];
+        for input in inputs {
+            let crate_name = crate_name(input);
+            if let Some(other) = crates.iter().find(|krate| krate.crate_name == crate_name) {
+                bail!(
+                    "Input files `{}` and `{}` have the same crate name `{crate_name}`",
+                    other.input.display(),
+                    input.display()
+                );
+            }
+            let metadata = standalone_artifact(&outdir, &crate_name, Metadata);
+            crates.push(StandaloneCrate { metadata, input: input.to_path_buf(), crate_name });
+        }
+        Ok(StandaloneProjectBuilder { outdir, crates, session })
     }
 
-    /// Build a project by compiling `self.input` file.
+    /// Build a project by compiling each input file.
     fn build(self) -> Result<Project> {
-        // Register artifacts that may be generated by the compiler / linker for future deletion.
-        let rlib_path = guess_rlib_name(&self.outdir.join(self.input.file_name().unwrap()));
-        self.session.record_temporary_file(&rlib_path);
-        self.session.record_temporary_file(&self.metadata.path);
-
-        // Build and link the artifacts.
-        debug!(krate=?self.crate_name, input=?self.input, ?rlib_path, "build compile");
-        self.session.compile_single_rust_file(&self.input, &self.crate_name, &self.outdir)?;
-
-        let metadata = if let Ok(goto_model) = Artifact::try_from(&self.metadata, SymTabGoto) {
-            metadata_with_function(
-                self.session,
-                &self.crate_name,
-                from_json(&self.metadata)?,
-                goto_model.path,
-            )
-        } else {
-            from_json(&self.metadata)?
-        };
+        let mut metadata = vec![];
+        let mut compile_time = Duration::ZERO;
+        let mut metadata_time = Duration::ZERO;
+        for krate in &self.crates {
+            // Register artifacts that may be generated by the compiler / linker for future
+            // deletion.
+            let rlib_path = guess_rlib_name(&self.outdir.join(krate.input.file_name().unwrap()));
+            self.session.record_temporary_file(&rlib_path);
+            self.session.record_temporary_file(&krate.metadata.path);
 
-        // Create the project with the artifacts built by the compiler.
-        let result = Project::try_new(self.session, self.outdir, vec![metadata], None, None);
-        if let Ok(project) = &result {
+            // Build the artifacts.
+            debug!(krate=?krate.crate_name, input=?krate.input, ?rlib_path, "build compile");
+            timed(&mut compile_time, || {
+                self.session.compile_single_rust_file(&krate.input, &krate.crate_name, &self.outdir)
+            })?;
+
+            metadata.push(timed(&mut metadata_time, || from_json(&krate.metadata))?);
+        }
+        timed(&mut metadata_time, || {
+            add_function_harnesses(&self.session.args.function, &self.crates, &mut metadata)
+        })?;
+
+        // Create the project with the artifacts built by the compiler. The artifacts are deleted
+        // at the end of the session unless the user asked to keep them, in which case the goto
+        // binaries may be reused by the next run.
+        let link_cache = self.session.args.keep_temps;
+        let mut result =
+            Project::try_new(self.session, self.outdir, metadata, None, None, link_cache);
+        if let Ok(project) = &mut result {
+            project.build_timings.compile = compile_time;
+            project.build_timings.metadata = metadata_time;
+            project.warn_duplicate_harnesses(self.session);
             self.session.record_temporary_files(&project.artifacts);
+            let manifest = project.outdir.join(MANIFEST_FILE);
+            self.session.record_temporary_file(&manifest);
+            project.write_manifest(&manifest)?;
         }
         result
     }
 }
 
-/// Generate a `KaniMetadata` by extending the original metadata to contain the function under
-/// verification, when there is one.
+/// Add a mock harness for each function given with `--function` to the metadata of the crate that
+/// defines it, where `metadata` has the metadata of each crate in `crates`.
+///
+/// A crate defines a function if its goto model includes the function symbol. If there is a
+/// single crate, we assume that it defines every function.
+fn add_function_harnesses(
+    functions: &[String],
+    crates: &[StandaloneCrate],
+    metadata: &mut [KaniMetadata],
+) -> Result<()> {
+    for function in functions {
+        let defined_in: Vec<usize> = if crates.len() == 1 {
+            vec![0]
+        } else {
+            (0..crates.len()).filter(|idx| defines_symbol(&crates[*idx], function)).collect()
+        };
+        match defined_in[..] {
+            [idx] => {
+                // The crate may not have a model if nothing was generated for it.
+                if let Ok(goto_model) = Artifact::try_from(&crates[idx].metadata, SymTabGoto) {
+                    let crate_name = crates[idx].crate_name.as_str();
+                    let harness =
+                        mock_proof_harness(function, None, Some(crate_name), Some(goto_model.path));
+                    metadata[idx].proof_harnesses.push(harness);
+                }
+            }
+            [] => bail!("function `{function}` is not defined in any of the input files"),
+            _ => bail!(
+                "function `{function}` is defined in more than one input file: `{}`",
+                defined_in
+                    .iter()
+                    .map(|idx| crates[*idx].input.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join("`, `")
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Check whether the goto model of the given crate includes the given symbol, based on its map of
+/// symbol names.
+fn defines_symbol(krate: &StandaloneCrate, symbol: &str) -> bool {
+    Artifact::try_from(&krate.metadata, PrettyNameMap)
+        .and_then(|names| from_json::<BTreeMap<String, Option<String>>>(&names))
+        .map_or(false, |names| names.contains_key(symbol))
+}
+
+/// Generate a `KaniMetadata` by extending the original metadata to contain the functions under
+/// verification, if any. All functions share the same model file.
 fn metadata_with_function(
-    session: &KaniSession,
+    functions: &[String],
     crate_name: &str,
     mut metadata: KaniMetadata,
     model_file: PathBuf,
 ) -> KaniMetadata {
-    if let Some(name) = &session.args.function {
-        // --function is untranslated, create a mock harness
-        metadata.proof_harnesses.push(mock_proof_harness(
-            name,
-            None,
-            Some(crate_name),
-            Some(model_file),
-        ));
-    }
+    // --function is untranslated, create a mock harness for each function
+    metadata.proof_harnesses.extend(
+        functions
+            .iter()
+            .map(|name| mock_proof_harness(name, None, Some(crate_name), Some(model_file.clone()))),
+    );
     metadata
 }
 
@@ -367,3 +1269,1159 @@ fn standalone_artifact(out_dir: &Path, crate_name: &String, typ: ArtifactType) -
     let _ = path.set_extension(&typ);
     Artifact { path, typ }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::StandaloneArgs;
+    use crate::harness_runner::HarnessRunner;
+    use crate::metadata::mock_proof_harness;
+    use clap::Parser;
+    use once_cell::sync::Lazy;
+    use std::collections::{BTreeSet, HashSet};
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Fake `goto-cc`: specializing a model copies it, and linking writes a mock goto binary and
+    /// appends a line to `<output>.links`, so tests can count how many times it was linked.
+    /// Linking also prints a warning, so tests can check the captured link output.
+    const MOCK_GOTO_CC: &str = r#"#!/bin/sh
+if [ "$1" = "--version" ]; then
+    echo "5.95.1 (mock)"
+    exit 0
+fi
+function=""
+while [ $# -gt 0 ]; do
+    case "$1" in
+        -o) output="$2"; shift ;;
+        --function) function="$2"; shift ;;
+        *) [ -z "$input" ] && input="$1" ;;
+    esac
+    shift
+done
+if [ -n "$function" ]; then
+    cp "$input" "$output"
+else
+    echo linked > "$output"
+    echo "$input" >> "$output.links"
+    echo "warning: linked $input" >&2
+fi
+"#;
+
+    /// Fake `goto-instrument`: append a line to its output, which is always the last argument.
+    const MOCK_GOTO_INSTRUMENT: &str = r#"#!/bin/sh
+for output; do :; done
+echo instrumented >> "$output"
+"#;
+
+    /// Fake `kani-compiler` that fails after writing a partial output to the output directory.
+    const MOCK_KANI_COMPILER: &str = r#"#!/bin/sh
+while [ $# -gt 0 ]; do
+    case "$1" in
+        --out-dir) outdir="$2"; shift ;;
+    esac
+    shift
+done
+echo partial > "$outdir/partial.symtab.out"
+exit 1
+"#;
+
+    /// The directory with fake versions of the compiler and the CBMC tools, so we can build projects
+    /// without them.
+    /// The tools are shared by every test, so we keep them until the process exits.
+    static MOCK_TOOLS: Lazy<PathBuf> = Lazy::new(|| {
+        let dir = temp_dir().into_path();
+        for (tool, script) in [
+            ("goto-cc", MOCK_GOTO_CC),
+            ("goto-instrument", MOCK_GOTO_INSTRUMENT),
+            ("kani-compiler", MOCK_KANI_COMPILER),
+        ] {
+            let path = dir.join(tool);
+            std::fs::write(&path, script).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        dir
+    });
+
+    /// Create a session with the given extra arguments that runs the mock CBMC tools.
+    fn mock_session(args: &[&str]) -> KaniSession {
+        let args = ["kani", "input.rs", "--quiet"].iter().chain(args);
+        KaniSession {
+            args: StandaloneArgs::try_parse_from(args).unwrap().verify_opts,
+            codegen_tests: false,
+            kani_compiler: MOCK_TOOLS.join("kani-compiler"),
+            kani_lib_c: PathBuf::from("kani_lib.c"),
+            goto_cc: MOCK_TOOLS.join("goto-cc"),
+            goto_instrument: MOCK_TOOLS.join("goto-instrument"),
+            goto_cc_version: Default::default(),
+            temporaries: Mutex::new(vec![]),
+            temporary_dirs: Mutex::new(vec![]),
+        }
+    }
+
+    /// The number of times the mock linker produced the given goto binary.
+    fn link_count(goto: &Path) -> usize {
+        let mut links = goto.as_os_str().to_owned();
+        links.push(".links");
+        std::fs::read_to_string(links).map_or(0, |links| links.lines().count())
+    }
+
+    /// Create an empty directory under the system temporary directory, which is deleted when the
+    /// returned guard is dropped. The directory path is canonical.
+    fn temp_dir() -> TempDir {
+        let parent = std::env::temp_dir().canonicalize().unwrap();
+        tempfile::Builder::new().prefix("kani_project_").tempdir_in(parent).unwrap()
+    }
+
+    /// Create the metadata of a crate with the given proof harnesses.
+    fn test_metadata(crate_name: &str, proof_harnesses: Vec<HarnessMetadata>) -> KaniMetadata {
+        KaniMetadata {
+            crate_name: crate_name.to_string(),
+            proof_harnesses,
+            unsupported_features: vec![],
+            test_harnesses: vec![],
+        }
+    }
+
+    /// Create a project with one harness per name, each one with its own goto model.
+    fn mock_project(outdir: &Path, names: &[&str]) -> Project {
+        let mut artifacts = vec![];
+        let proof_harnesses = names
+            .iter()
+            .map(|name| {
+                let symtab_goto = outdir.join(name).with_extension(&SymTabGoto);
+                std::fs::write(&symtab_goto, name.as_bytes()).unwrap();
+                artifacts.push(Artifact::try_new(&symtab_goto, SymTabGoto).unwrap());
+                mock_proof_harness(name, None, Some("krate"), Some(symtab_goto))
+            })
+            .collect();
+        let metadata = test_metadata("krate", proof_harnesses);
+        Project {
+            metadata: vec![metadata],
+            outdir: outdir.to_path_buf(),
+            artifacts,
+            ..Default::default()
+        }
+    }
+
+    /// Mock the outputs of a cargo build of a package without dependencies, given the metadata of
+    /// the crates it produced.
+    fn mock_cargo_outputs(outdir: &Path, metadata: Vec<Artifact>) -> CargoOutputs {
+        let manifest = outdir.join("Cargo.toml");
+        std::fs::write(&manifest, "[package]\nname = \"lib\"\nversion = \"0.1.0\"\n").unwrap();
+        std::fs::create_dir_all(outdir.join("src")).unwrap();
+        std::fs::write(outdir.join("src/lib.rs"), "").unwrap();
+        let cargo_metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(manifest)
+            .other_options(vec!["--offline".to_string()])
+            .exec()
+            .unwrap();
+        CargoOutputs {
+            outdir: outdir.to_path_buf(),
+            metadata,
+            cargo_metadata,
+            failed_targets: None,
+        }
+    }
+
+    /// Create a project with the given harnesses and no artifacts.
+    fn project_with_harnesses(proof_harnesses: Vec<HarnessMetadata>) -> Project {
+        let metadata = test_metadata("krate", proof_harnesses);
+        Project { metadata: vec![metadata], ..Default::default() }
+    }
+
+    #[test]
+    fn check_get_harness() {
+        let mut duplicate = mock_proof_harness("module::check", None, Some("other"), None);
+        duplicate.mangled_name = "_mangled_check".to_string();
+        let project = project_with_harnesses(vec![
+            mock_proof_harness("check", None, None, None),
+            mock_proof_harness("module::check", None, None, None),
+            duplicate,
+        ]);
+
+        assert_eq!(project.get_harness("check").unwrap().pretty_name, "check");
+        assert_eq!(project.get_harness("module::check").unwrap().crate_name, "<unknown>");
+        assert_eq!(project.get_harness("_mangled_check").unwrap().crate_name, "other");
+        assert!(project.get_harness("module").is_none());
+        assert_eq!(project.get_harness("other::module::check").unwrap().crate_name, "other");
+    }
+
+    #[test]
+    fn check_duplicate_harness_names() {
+        let project = project_with_harnesses(vec![
+            mock_proof_harness("check", None, Some("first"), None),
+            mock_proof_harness("check", None, Some("second"), None),
+            mock_proof_harness("module::check", None, Some("first"), None),
+            mock_proof_harness("module::check", None, Some("first"), None),
+        ]);
+        assert_eq!(project.duplicate_harness_names(), ["check"]);
+        assert_eq!(project.get_harness("first::check").unwrap().crate_name, "first");
+        assert_eq!(project.get_harness("second::check").unwrap().crate_name, "second");
+    }
+
+    #[test]
+    fn check_get_harnesses_matching() {
+        let mut mangled = mock_proof_harness("other::verify", None, None, None);
+        mangled.mangled_name = "_mangled_check_verify".to_string();
+        let project = project_with_harnesses(vec![
+            mock_proof_harness("check_one", None, None, None),
+            mock_proof_harness("module::check_two", None, None, None),
+            mock_proof_harness("module::check_two", None, Some("other"), None),
+            mangled,
+        ]);
+
+        let names = |pattern| {
+            project
+                .get_harnesses_matching(pattern)
+                .unwrap()
+                .iter()
+                .map(|harness| harness.pretty_name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("check_*"), ["check_one"]);
+        assert_eq!(names("module::*"), ["module::check_two", "module::check_two"]);
+        assert_eq!(
+            names("*check*"),
+            ["check_one", "module::check_two", "module::check_two", "other::verify"]
+        );
+        assert!(names("check").is_empty());
+        assert!(project.get_harnesses_matching("[").is_err());
+    }
+
+    #[test]
+    fn check_unique_models() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let project = mock_project(&outdir, &["first", "second", "third"]);
+        let mut metadata = project.metadata[0].clone();
+        let models = metadata.proof_harnesses.clone();
+        metadata.proof_harnesses = (0..50)
+            .map(|idx| {
+                let model = models[idx % models.len()].goto_file.clone();
+                mock_proof_harness(&format!("harness_{idx}"), None, Some("krate"), model)
+            })
+            .collect();
+
+        let unique = unique_models(&[metadata.clone()]).unwrap();
+        assert_eq!(unique.len(), 3);
+        assert!(unique.iter().all(|model| model.has_type(SymTabGoto)));
+
+        // Building the project links each model exactly once, even when linking in parallel.
+        let session = mock_session(&["--enable-unstable", "-j"]);
+        let project =
+            Project::try_new(&session, outdir.to_path_buf(), vec![metadata], None, None, true)
+                .unwrap();
+        let gotos: Vec<_> = project.artifacts_of_type(Goto).collect();
+        assert_eq!(gotos.len(), 3);
+        assert_eq!(gotos.iter().map(|goto| link_count(goto)).sum::<usize>(), 3);
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_link_cache_after_verification() {
+        let session = mock_session(&[]);
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let metadata = mock_project(&outdir, &["first", "second"]).metadata;
+        let project =
+            Project::try_new(&session, outdir.to_path_buf(), metadata.clone(), None, None, true)
+                .unwrap();
+
+        // Instrumenting the harnesses leaves the linked models untouched.
+        let runner = HarnessRunner { sess: &session, project: &project };
+        let gotos: Vec<_> = project.artifacts_of_type(Goto).map(|goto| goto.path.clone()).collect();
+        for harness in project.get_all_harnesses() {
+            let instrumented = runner.instrument_harness(harness).unwrap();
+            assert!(!gotos.contains(&instrumented));
+            assert!(std::fs::read_to_string(instrumented).unwrap().contains("instrumented"));
+        }
+        for goto in &gotos {
+            assert_eq!(std::fs::read_to_string(goto).unwrap(), "linked\n");
+        }
+
+        // So the next build reuses them.
+        let project =
+            Project::try_new(&session, outdir.to_path_buf(), metadata, None, None, true).unwrap();
+        assert_eq!(project.artifacts_of_type(Goto).count(), 2);
+        assert!(gotos.iter().all(|goto| link_count(goto) == 1));
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_link_without_cache() {
+        let session = mock_session(&[]);
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let metadata = mock_project(&outdir, &["first"]).metadata;
+
+        // Every build links the model again, and no cache information is stored.
+        for links in 1..=2 {
+            let project = Project::try_new(
+                &session,
+                outdir.to_path_buf(),
+                metadata.clone(),
+                None,
+                None,
+                false,
+            )
+            .unwrap();
+            let goto = project.artifacts_of_type(Goto).next().unwrap();
+            assert_eq!(link_count(goto), links);
+            assert!(!link_cache_file(goto).exists());
+        }
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_missing_model_error() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let mut metadata = mock_project(&outdir, &["first"]).metadata.remove(0);
+        metadata.proof_harnesses.push(mock_proof_harness("second", None, Some("krate"), None));
+
+        let error = unique_models(&[metadata]).unwrap_err().to_string();
+        assert!(error.starts_with("harness `second` has no goto model"));
+    }
+
+    #[test]
+    fn check_remove_harnesses_without_model() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let mut metadata = mock_project(&outdir, &["first"]).metadata;
+        let broken = outdir.join("broken.symtab.out");
+        metadata[0].proof_harnesses.push(mock_proof_harness(
+            "broken",
+            None,
+            Some("krate"),
+            Some(broken),
+        ));
+        metadata[0].test_harnesses.push(mock_proof_harness("no_model", None, Some("krate"), None));
+
+        let removed = remove_harnesses_without_model(&mut metadata);
+        let removed: Vec<_> = removed.iter().map(|harness| harness.pretty_name.as_str()).collect();
+        assert_eq!(removed, ["broken", "no_model"]);
+        assert_eq!(metadata[0].proof_harnesses.len(), 1);
+        assert!(metadata[0].test_harnesses.is_empty());
+        assert_eq!(unique_models(&metadata).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn check_harness_link_log() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let mut project = mock_project(&outdir, &["first", "second"]);
+        for harness in &project.metadata[0].proof_harnesses {
+            let model = harness.goto_file.as_ref().unwrap();
+            let goto = convert_type(model, SymTabGoto, Goto);
+            std::fs::write(&goto, "").unwrap();
+            project.artifacts.push(Artifact::try_new(&goto, Goto).unwrap());
+        }
+        let goto = outdir.join("first").with_extension(&Goto);
+        project.link_logs.insert(goto, "warning: unused symbol".to_string());
+
+        let harnesses = project.get_all_harnesses();
+        assert_eq!(project.get_harness_link_log(harnesses[0]), Some("warning: unused symbol"));
+        assert_eq!(project.get_harness_link_log(harnesses[1]), None);
+    }
+
+    #[test]
+    fn check_link_log_from_linker() {
+        for verbose in [false, true] {
+            let mut session = mock_session(&[]);
+            session.args.common_args.quiet = !verbose;
+            session.args.common_args.verbose = verbose;
+            let outdir_tmp = temp_dir();
+            let outdir = outdir_tmp.path();
+            let metadata = mock_project(&outdir, &["first"]).metadata;
+            let model = metadata[0].proof_harnesses[0].goto_file.clone().unwrap();
+            let expected = format!("warning: linked {}\n", model.display());
+
+            // The linker output is captured even when it is printed, and it is kept when the goto
+            // binary is reused by the next build.
+            for _ in 0..2 {
+                let project = Project::try_new(
+                    &session,
+                    outdir.to_path_buf(),
+                    metadata.clone(),
+                    None,
+                    None,
+                    true,
+                )
+                .unwrap();
+                let harness = project.get_all_harnesses()[0];
+                assert_eq!(project.get_harness_link_log(harness), Some(expected.as_str()));
+                assert_eq!(link_count(&convert_type(&model, SymTabGoto, Goto)), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn check_manifest_round_trip() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let mut project = mock_project(&outdir, &["first", "second"]);
+        project.failed_targets = Some(vec!["broken".to_string()]);
+        let path = outdir.join(MANIFEST_FILE);
+        project.write_manifest(&path).unwrap();
+
+        let manifest: ProjectManifest = from_json(&path).unwrap();
+        assert_eq!(manifest, project.manifest());
+        assert_eq!(manifest.crates, ["krate"]);
+        assert_eq!(manifest.harnesses.len(), 2);
+        assert_eq!(
+            manifest.harnesses[1].artifacts[&SymTabGoto],
+            outdir.join("second").with_extension(&SymTabGoto)
+        );
+    }
+
+    /// Write the metadata and the goto model of a crate with one harness to the given directory.
+    fn write_crate_metadata(dir: &Path, crate_name: &str) -> Artifact {
+        let symtab_goto = dir.join(crate_name).with_extension(&SymTabGoto);
+        std::fs::write(&symtab_goto, crate_name).unwrap();
+        let harness = mock_proof_harness("harness", None, Some(crate_name), Some(symtab_goto));
+        let metadata = test_metadata(crate_name, vec![harness]);
+        let path = dir.join(crate_name).with_extension(&Metadata);
+        dump_metadata(&metadata, &path);
+        Artifact::try_new(&path, Metadata).unwrap()
+    }
+
+    #[test]
+    fn check_read_metadata() {
+        let cargo_dir_tmp = temp_dir();
+        let cargo_dir = cargo_dir_tmp.path();
+        let standalone_dir_tmp = temp_dir();
+        let standalone_dir = standalone_dir_tmp.path();
+        let outputs = [
+            write_crate_metadata(&cargo_dir, "lib"),
+            write_crate_metadata(&standalone_dir, "single"),
+        ];
+
+        let metadata = read_metadata(&outputs).unwrap();
+        let crates: Vec<_> = metadata.iter().map(|md| md.crate_name.as_str()).collect();
+        assert_eq!(crates, ["lib", "single"]);
+        let goto_files: Vec<_> =
+            metadata.iter().map(|md| md.proof_harnesses[0].goto_file.clone().unwrap()).collect();
+        assert_eq!(
+            goto_files,
+            [cargo_dir.join("lib.symtab.out"), standalone_dir.join("single.symtab.out")]
+        );
+    }
+
+    #[test]
+    fn check_merge() {
+        let lib_dir_tmp = temp_dir();
+        let lib_dir = lib_dir_tmp.path();
+        let test_dir_tmp = temp_dir();
+        let test_dir = test_dir_tmp.path();
+        let mut lib = mock_project(&lib_dir, &["lib_check"]);
+        lib.failed_targets = Some(vec!["bench".to_string()]);
+        let mut test = mock_project(&test_dir, &["test_check"]);
+        test.metadata[0].crate_name = "tests".to_string();
+        test.failed_targets = Some(vec!["example".to_string()]);
+
+        let project = lib.merge(test).unwrap();
+        assert_eq!(project.outdir, lib_dir);
+        let names: Vec<_> = project
+            .get_all_harnesses()
+            .iter()
+            .map(|harness| harness.pretty_name.as_str())
+            .collect();
+        assert_eq!(names, ["lib_check", "test_check"]);
+        for (name, dir) in [("lib_check", &lib_dir), ("test_check", &test_dir)] {
+            let harness = project.get_harness(name).unwrap();
+            let model = project.get_harness_artifact(harness, SymTabGoto).unwrap();
+            assert_eq!(model.path, dir.join(name).with_extension(&SymTabGoto));
+        }
+        assert_eq!(project.failed_targets, Some(vec!["bench".to_string(), "example".to_string()]));
+
+        // The same crate cannot be included twice.
+        let err = project.merge(mock_project(&test_dir, &["other"])).unwrap_err();
+        assert!(err.to_string().contains("crate `krate` was included more than once"));
+
+        // Projects with linked artifacts cannot be merged.
+        let merged = Project { merged_artifacts: true, ..Default::default() };
+        assert!(merged.merge(Project::default()).is_err());
+        assert!(
+            Project::default()
+                .merge(Project { merged_artifacts: true, ..Default::default() })
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn check_from_mixed() {
+        let session = mock_session(&[]);
+        let cargo_dir_tmp = temp_dir();
+        let cargo_dir = cargo_dir_tmp.path();
+        let standalone_dir_tmp = temp_dir();
+        let standalone_dir = standalone_dir_tmp.path();
+        let cargo_md = write_crate_metadata(&cargo_dir, "lib");
+        let standalone_md = write_crate_metadata(&standalone_dir, "single");
+        let outputs = mock_cargo_outputs(&cargo_dir, vec![cargo_md]);
+
+        // The project includes the harnesses and artifacts of both sides.
+        let project = Project::from_mixed(outputs, &[standalone_md.clone()], &session).unwrap();
+        assert_eq!(project.outdir, cargo_dir);
+        assert!(project.cargo_metadata.is_some());
+        assert!(!project.merged_artifacts);
+        for (krate, dir) in [("lib", &cargo_dir), ("single", &standalone_dir)] {
+            let harness = project.get_harness(&format!("{krate}::harness")).unwrap();
+            assert_eq!(harness.crate_name, krate);
+            let model = project.get_harness_artifact(harness, SymTabGoto).unwrap();
+            assert_eq!(model.path, dir.join(krate).with_extension(&SymTabGoto));
+            let goto = project.get_harness_artifact(harness, Goto).unwrap();
+            assert_eq!(goto.path, dir.join(krate).with_extension(&Goto));
+            assert_eq!(link_count(goto), 1);
+        }
+        assert_eq!(project.artifacts_of_type(Goto).count(), 2);
+
+        // A crate cannot be built by cargo and provided as a standalone output.
+        let outputs = mock_cargo_outputs(&cargo_dir, vec![standalone_md.clone()]);
+        let err = Project::from_mixed(outputs, &[standalone_md], &session).unwrap_err();
+        assert!(format!("{err:#}").contains("crate `single` was included more than once"));
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_metadata_with_multiple_functions() {
+        let model_file = PathBuf::from("cbmc-linked.symtab.out");
+        let functions = ["foo".to_string(), "bar".to_string()];
+        let metadata = test_metadata("krate", vec![]);
+        let metadata = metadata_with_function(&functions, "krate", metadata, model_file.clone());
+        let names: Vec<_> =
+            metadata.proof_harnesses.iter().map(|harness| harness.pretty_name.as_str()).collect();
+        assert_eq!(names, ["foo", "bar"]);
+        assert!(
+            metadata
+                .proof_harnesses
+                .iter()
+                .all(|harness| harness.goto_file.as_ref() == Some(&model_file))
+        );
+    }
+
+    #[test]
+    fn check_function_harnesses_per_crate() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        let mut crates = vec![];
+        let mut metadata = vec![];
+        for (crate_name, symbols) in [("first", ["foo", "shared"]), ("second", ["bar", "shared"])] {
+            let md = write_crate_metadata(&dir, crate_name);
+            let names: BTreeMap<_, _> = symbols.iter().map(|symbol| (symbol, symbol)).collect();
+            let names_file = convert_type(&md.path, Metadata, PrettyNameMap);
+            serde_json::to_writer(File::create(names_file).unwrap(), &names).unwrap();
+            metadata.push(from_json(&md).unwrap());
+            let input = dir.join(crate_name).with_extension("rs");
+            crates.push(StandaloneCrate { metadata: md, input, crate_name: crate_name.into() });
+        }
+
+        // Each harness is only added to the crate that defines its function.
+        let functions = ["foo".to_string(), "bar".to_string()];
+        add_function_harnesses(&functions, &crates, &mut metadata).unwrap();
+        let names = |md: &KaniMetadata| {
+            md.proof_harnesses.iter().map(|harness| harness.pretty_name.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(names(&metadata[0]), ["harness", "foo"]);
+        assert_eq!(names(&metadata[1]), ["harness", "bar"]);
+        assert_eq!(metadata[1].proof_harnesses[1].crate_name, "second");
+
+        let error = add_function_harnesses(&["baz".to_string()], &crates, &mut metadata);
+        assert!(error.unwrap_err().to_string().contains("not defined in any of the input files"));
+        let error = add_function_harnesses(&["shared".to_string()], &crates, &mut metadata);
+        assert!(error.unwrap_err().to_string().contains("defined in more than one input file"));
+
+        // A single crate is assumed to define every function.
+        let mut single = metadata[..1].to_vec();
+        add_function_harnesses(&["baz".to_string()], &crates[..1], &mut single).unwrap();
+        assert_eq!(names(&single[0]), ["harness", "foo", "baz"]);
+    }
+
+    #[test]
+    fn check_total_artifact_size() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let project = mock_project(&outdir, &["a", "bb", "ccc"]);
+        assert_eq!(project.artifacts[1].size_bytes().unwrap(), 2);
+        assert_eq!(project.total_artifact_size(SymTabGoto).unwrap(), 6);
+        assert_eq!(project.total_artifact_size(Goto).unwrap(), 0);
+
+        std::fs::remove_file(outdir.join("bb.symtab.out")).unwrap();
+        assert!(project.total_artifact_size(SymTabGoto).is_err());
+    }
+
+    #[test]
+    fn check_artifacts_of_type() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let mut project = mock_project(&outdir, &["first", "second"]);
+        for name in ["first", "second"] {
+            let goto = outdir.join(name).with_extension(&Goto);
+            std::fs::write(&goto, name).unwrap();
+            project.artifacts.push(Artifact::try_new(&goto, Goto).unwrap());
+        }
+
+        let models: HashSet<_> = project
+            .get_all_harnesses()
+            .into_iter()
+            .filter_map(|harness| harness.goto_file.as_ref())
+            .collect();
+        assert_eq!(project.artifacts_of_type(Goto).count(), models.len());
+        assert_eq!(project.artifacts_of_type(SymTabGoto).count(), models.len());
+        assert_eq!(project.artifacts_of_type(TypeMap).count(), 0);
+        assert!(
+            project.artifacts_of_type(Goto).all(|goto| goto.path.extension().unwrap() == "out")
+        );
+    }
+
+    #[test]
+    fn check_artifact_errors() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let missing = outdir.join("missing.symtab.out");
+        let err = Artifact::try_new(&missing, SymTabGoto).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Failed to process {}: file does not exist", missing.display())
+        );
+
+        #[cfg(unix)]
+        {
+            let link = outdir.join("link.symtab.out");
+            std::os::unix::fs::symlink(&missing, &link).unwrap();
+            let err = Artifact::try_new(&link, SymTabGoto).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "Failed to process {}: symbolic link target `{}` does not exist",
+                    link.display(),
+                    missing.display()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn check_from_goto_files() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let mut harnesses = vec![];
+        let mut gotos = vec![];
+        for name in ["first", "second"] {
+            let goto = outdir.join(name).with_extension(&Goto);
+            std::fs::write(&goto, name).unwrap();
+            gotos.push(goto);
+            let symtab_goto = outdir.join(name).with_extension(&SymTabGoto);
+            harnesses.push(mock_proof_harness(name, None, Some("krate"), Some(symtab_goto)));
+        }
+        let metadata = test_metadata("krate", harnesses);
+
+        // Each harness is matched with its own goto binary.
+        let project = Project::from_goto_files(&gotos, metadata.clone()).unwrap();
+        assert_eq!(project.outdir, outdir);
+        assert!(!project.merged_artifacts);
+        for (harness, goto) in project.get_all_harnesses().into_iter().zip(&gotos) {
+            assert_eq!(project.get_harness_artifact(harness, Goto).unwrap().path, *goto);
+        }
+
+        // A single goto binary is used for every harness.
+        let project = Project::from_goto_files(&gotos[1..], metadata.clone()).unwrap();
+        assert!(project.merged_artifacts);
+        for harness in project.get_all_harnesses() {
+            assert_eq!(project.get_harness_artifact(harness, Goto).unwrap().path, gotos[1]);
+        }
+
+        assert!(Project::from_goto_files(&[], metadata.clone()).is_err());
+        assert!(Project::from_goto_files(&[outdir.join("missing.out")], metadata.clone()).is_err());
+
+        // With multiple goto binaries, every harness must match one of them.
+        let mut unmatched = metadata.clone();
+        let third = outdir.join("third").with_extension(&SymTabGoto);
+        unmatched.proof_harnesses.push(mock_proof_harness(
+            "third",
+            None,
+            Some("krate"),
+            Some(third),
+        ));
+        unmatched.proof_harnesses.push(mock_proof_harness("fourth", None, Some("krate"), None));
+        let error = Project::from_goto_files(&gotos, unmatched).unwrap_err().to_string();
+        assert!(error.ends_with("`third`, `fourth`"), "{error}");
+    }
+
+    #[test]
+    fn check_goto_project_outdir() {
+        let session = mock_session(&[]);
+        let input_dir_tmp = temp_dir();
+        let input_dir = input_dir_tmp.path();
+        let input = input_dir.join("model.out");
+        std::fs::write(&input, "goto").unwrap();
+        let md_file = input_dir.join("model.kani-metadata.json");
+        let harness = mock_proof_harness("check", None, Some("model"), None);
+        let metadata = test_metadata("model", vec![harness]);
+        dump_metadata(&metadata, &md_file);
+
+        // The goto binary is copied to a temporary directory, which is deleted with the session.
+        let project = goto_project(&input, &md_file, &session).unwrap();
+        assert_ne!(project.outdir, input_dir);
+        let goto = project.get_harness_artifact(project.get_all_harnesses()[0], Goto).unwrap();
+        assert!(goto.starts_with(&project.outdir));
+        assert_eq!(std::fs::read_dir(&input_dir).unwrap().count(), 2);
+        drop(session);
+        assert!(!project.outdir.exists());
+    }
+
+    #[test]
+    fn check_goto_project_gen_c() {
+        let session = mock_session(&["--enable-unstable", "--gen-c"]);
+        let input_tmp = temp_dir();
+        let input_dir = input_tmp.path();
+        let input = input_dir.join("model.out");
+        std::fs::write(&input, "goto").unwrap();
+        let md_file = input_dir.join("model.kani-metadata.json");
+        let harness = mock_proof_harness("check", None, Some("model"), None);
+        dump_metadata(&test_metadata("model", vec![harness]), &md_file);
+
+        // There is no pretty name map for a goto binary built outside of Kani, so the C code is
+        // generated without being demangled.
+        let project = goto_project(&input, &md_file, &session).unwrap();
+        let runner = HarnessRunner { sess: &session, project: &project };
+        let instrumented = runner.instrument_harness(project.get_all_harnesses()[0]).unwrap();
+        assert!(alter_extension(&instrumented, "c").exists());
+        assert!(!alter_extension(&instrumented, "demangled.c").exists());
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_standalone_outdir_failed_build() {
+        let session = mock_session(&[]);
+        let input_tmp = temp_dir();
+        let input = input_tmp.path().join("krate.rs");
+        std::fs::write(&input, "").unwrap();
+
+        // The temporary output directory is deleted with the session, even if the compiler failed
+        // and left partial outputs behind.
+        let builder = StandaloneProjectBuilder::try_new(&[input], &session).unwrap();
+        let outdir = builder.outdir.clone();
+        assert!(builder.build().is_err());
+        assert!(outdir.join("partial.symtab.out").exists());
+        drop(session);
+        assert!(!outdir.exists());
+        assert_eq!(std::fs::read_dir(input_tmp.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn check_expected_artifact() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let mut project = mock_project(&outdir, &["first"]);
+        let harness = project.metadata[0].proof_harnesses[0].clone();
+        let goto_path = outdir.join("first.out");
+        let goto = Artifact::expected(&outdir.join("./other/../first.out"), Goto).unwrap();
+        assert_eq!(goto.path, goto_path);
+        project.artifacts.push(goto);
+
+        // The artifact is still resolved once the file has been created.
+        std::fs::write(&goto_path, "goto").unwrap();
+        assert_eq!(project.get_harness_artifact(&harness, Goto).unwrap().path, goto_path);
+        assert_eq!(Artifact::try_new(&goto_path, Goto).unwrap(), project.artifacts[1]);
+    }
+
+    #[test]
+    fn check_ensure_outdir() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        let outdir = dir.join("nested").join("outdir");
+        assert_eq!(ensure_outdir(&outdir).unwrap(), outdir);
+        assert!(outdir.is_dir());
+        // Existing directories are accepted as well.
+        assert_eq!(ensure_outdir(&outdir.join("..").join("outdir")).unwrap(), outdir);
+
+        let file = dir.join("file");
+        std::fs::write(&file, "").unwrap();
+        let err = ensure_outdir(&file.join("outdir")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("cannot create output directory {}", file.join("outdir").display())
+        );
+    }
+
+    #[test]
+    fn check_ensure_outdir_not_writable() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+        // Permissions are not enforced for privileged users, in which case we use a directory of
+        // a virtual file system where files cannot be created.
+        let outdir = if File::create(dir.join("file")).is_ok() {
+            PathBuf::from("/proc/self")
+        } else {
+            dir.to_path_buf()
+        };
+        let err = ensure_outdir(&outdir).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("cannot write to output directory {}", outdir.display())
+        );
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn check_timed() {
+        let mut duration = Duration::ZERO;
+        let result = timed(&mut duration, || {
+            std::thread::sleep(Duration::from_millis(1));
+            "done"
+        });
+        assert_eq!(result, "done");
+        let first = duration;
+        assert!(first >= Duration::from_millis(1));
+
+        // Durations are accumulated.
+        timed(&mut duration, || std::thread::sleep(Duration::from_millis(1)));
+        assert!(duration >= first + Duration::from_millis(1));
+    }
+
+    #[test]
+    fn check_build_timings() {
+        let session = mock_session(&[]);
+        let cargo_dir_tmp = temp_dir();
+        let cargo_dir = cargo_dir_tmp.path();
+        let standalone_dir_tmp = temp_dir();
+        let standalone_dir = standalone_dir_tmp.path();
+        let cargo_md = write_crate_metadata(&cargo_dir, "lib");
+        let standalone_md = write_crate_metadata(&standalone_dir, "single");
+        let outputs = mock_cargo_outputs(&cargo_dir, vec![cargo_md]);
+
+        // Every phase of the build is recorded, including the link of each model.
+        let project = Project::from_mixed(outputs, &[standalone_md], &session).unwrap();
+        let timings = &project.build_timings;
+        let gotos: BTreeSet<_> =
+            project.artifacts_of_type(Goto).map(|goto| goto.path.clone()).collect();
+        assert_eq!(timings.link.keys().cloned().collect::<BTreeSet<_>>(), gotos);
+        assert!(timings.link.values().all(|link| *link > Duration::ZERO));
+        assert!(timings.metadata > Duration::ZERO);
+        assert!(timings.total() >= timings.metadata + timings.link.values().sum::<Duration>());
+        assert_eq!(project.manifest().build_timings, *timings);
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_clean() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let project = mock_project(&outdir, &["first", "second"]);
+        project.write_manifest(&outdir.join(MANIFEST_FILE)).unwrap();
+        assert_eq!(std::fs::read_dir(&outdir).unwrap().count(), 3);
+
+        project.clean().unwrap();
+        assert_eq!(std::fs::read_dir(&outdir).unwrap().count(), 0);
+        // Cleaning again is a no-op.
+        project.clean().unwrap();
+    }
+
+    #[test]
+    fn check_clean_keeps_user_inputs() {
+        let session = mock_session(&[]);
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let user_dir_tmp = temp_dir();
+        let user_dir = user_dir_tmp.path();
+
+        // Goto binaries provided by the user.
+        let goto = user_dir.join("user.out");
+        std::fs::write(&goto, "goto").unwrap();
+        let metadata =
+            test_metadata("user", vec![mock_proof_harness("harness", None, Some("user"), None)]);
+        let project = Project::from_goto_files(&[goto.clone()], metadata).unwrap();
+        project.clean().unwrap();
+        assert!(goto.exists());
+
+        // Crates compiled standalone by the user.
+        let standalone_md = write_crate_metadata(&user_dir, "single");
+        let cargo_md = write_crate_metadata(&outdir, "lib");
+        let outputs = mock_cargo_outputs(&outdir, vec![cargo_md]);
+        let project = Project::from_mixed(outputs, &[standalone_md.clone()], &session).unwrap();
+        project.clean().unwrap();
+        assert!(!outdir.join("lib.out").exists());
+        assert!(standalone_md.exists());
+        assert!(user_dir.join("single.symtab.out").exists());
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_clean_between_cargo_builds() {
+        let session = mock_session(&[]);
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let cargo_md = write_crate_metadata(&outdir, "lib");
+        let goto = outdir.join("lib.out");
+
+        // Cargo does not rebuild crates whose outputs are fresh, so cleaning must keep the
+        // compiler outputs for the next build to succeed.
+        for links in 1..=2 {
+            let outputs = mock_cargo_outputs(&outdir, vec![cargo_md.clone()]);
+            let project = Project::from_mixed(outputs, &[], &session).unwrap();
+            assert_eq!(link_count(&goto), links);
+            project.clean().unwrap();
+            assert!(!goto.exists());
+            assert!(!link_cache_file(&goto).exists());
+            assert!(cargo_md.exists());
+            assert!(outdir.join("lib.symtab.out").exists());
+        }
+
+        drop(session);
+    }
+
+    #[test]
+    fn check_failed_targets_report() {
+        let mut project = project_with_harnesses(vec![]);
+        assert_eq!(project.failed_targets_report(), None);
+
+        project.failed_targets = Some(vec![]);
+        assert_eq!(project.failed_targets_report().unwrap(), r#"{"count":0,"targets":[]}"#);
+
+        project.failed_targets = Some(vec!["lib".to_string(), "bin".to_string()]);
+        assert_eq!(
+            project.failed_targets_report().unwrap(),
+            r#"{"count":2,"targets":["lib","bin"]}"#
+        );
+    }
+
+    #[test]
+    fn check_verified_lock_detects_changes() {
+        let outdir_tmp = temp_dir();
+        let outdir = outdir_tmp.path();
+        let project = mock_project(&outdir, &["first", "second"]);
+        let lock = outdir.join("kani-verified.lock");
+        project.write_verified_lock(&lock).unwrap();
+        assert!(project.check_verified_lock(&lock).is_ok());
+
+        std::fs::write(outdir.join("second.symtab.out"), "changed").unwrap();
+        let error = project.check_verified_lock(&lock).unwrap_err().to_string();
+        assert!(error.contains("`krate::second`"));
+        assert!(!error.contains("`krate::first`"));
+    }
+
+    #[test]
+    fn check_verified_lock_same_name_in_different_crates() {
+        let first_dir_tmp = temp_dir();
+        let first_dir = first_dir_tmp.path();
+        let second_dir_tmp = temp_dir();
+        let second_dir = second_dir_tmp.path();
+        let mut first = mock_project(&first_dir, &["check"]);
+        let mut second = mock_project(&second_dir, &["check"]);
+        for (project, krate) in [(&mut first, "first"), (&mut second, "second")] {
+            project.metadata[0].crate_name = krate.to_string();
+            project.metadata[0].proof_harnesses[0].crate_name = krate.to_string();
+        }
+        let project = first.merge(second).unwrap();
+        let digests = project.harness_digests().unwrap();
+        assert_eq!(digests.keys().collect::<Vec<_>>(), ["first::check", "second::check"]);
+
+        let lock = first_dir.join("kani-verified.lock");
+        project.write_verified_lock(&lock).unwrap();
+        std::fs::write(second_dir.join("check.symtab.out"), "changed").unwrap();
+        let error = project.check_verified_lock(&lock).unwrap_err().to_string();
+        assert!(error.contains("`second::check`"));
+        assert!(!error.contains("`first::check`"));
+    }
+
+    #[test]
+    fn check_verified_lock_goto_files() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        let goto = dir.join("model.out");
+        std::fs::write(&goto, "goto").unwrap();
+        let metadata =
+            test_metadata("krate", vec![mock_proof_harness("check", None, Some("krate"), None)]);
+        let project = Project::from_goto_files(&[goto.clone()], metadata).unwrap();
+        let lock = dir.join("kani-verified.lock");
+        project.write_verified_lock(&lock).unwrap();
+        assert!(project.check_verified_lock(&lock).is_ok());
+
+        std::fs::write(&goto, "changed").unwrap();
+        assert!(project.check_verified_lock(&lock).is_err());
+    }
+
+    /// Compute the digest of the source files under the given directory.
+    fn digests(dir: &Path, skip_dirs: &[PathBuf]) -> BTreeMap<PathBuf, SourceDigest> {
+        let mut sources = vec![];
+        collect_source_files(dir, skip_dirs, &mut HashSet::new(), &mut sources).unwrap();
+        sources
+            .into_iter()
+            .map(|source| {
+                let file_metadata = std::fs::metadata(&source).unwrap();
+                let digest = SourceDigest {
+                    len: file_metadata.len(),
+                    modified: file_metadata.modified().unwrap(),
+                    digest: file_digest(&source).unwrap(),
+                };
+                (source, digest)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn check_build_fingerprint() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        let target_dir = dir.join("target");
+        for subdir in ["src", "target", ".git"] {
+            std::fs::create_dir_all(dir.join(subdir)).unwrap();
+        }
+        for file in ["Cargo.toml", "src/lib.rs", "src/notes.txt", "target/out.rs", ".git/hook.rs"] {
+            std::fs::write(dir.join(file), file).unwrap();
+        }
+        let goto_file = target_dir.join("lib.symtab.out");
+        std::fs::write(&goto_file, "goto").unwrap();
+        let metadata_file = target_dir.join("lib.kani-metadata.json");
+        let harness = mock_proof_harness("harness", None, Some("lib"), Some(goto_file.clone()));
+        dump_metadata(&test_metadata("lib", vec![harness]), &metadata_file);
+
+        // Hidden and target directories are skipped.
+        let sources = digests(&dir, &[target_dir.clone()]);
+        let files: Vec<_> = sources.keys().cloned().collect();
+        assert_eq!(
+            files,
+            [dir.join("Cargo.toml"), dir.join("src/lib.rs"), dir.join("src/notes.txt")]
+        );
+
+        let fingerprint = BuildFingerprint {
+            args: "args".to_string(),
+            sources: sources.clone(),
+            metadata: vec![metadata_file.clone()],
+            failed_targets: None,
+        };
+        let fingerprint_file = target_dir.join(BUILD_FINGERPRINT_FILE);
+        fingerprint.write(&fingerprint_file).unwrap();
+        assert_eq!(from_json::<BuildFingerprint>(&fingerprint_file).unwrap(), fingerprint);
+        assert!(fingerprint.is_fresh("args", &sources));
+        assert!(!fingerprint.is_fresh("other", &sources));
+
+        // Editing a source file makes the build stale.
+        std::fs::write(dir.join("src/lib.rs"), "fn edited() {}").unwrap();
+        let new_sources = digests(&dir, &[target_dir.clone()]);
+        assert!(!fingerprint.is_fresh("args", &new_sources));
+
+        // So does removing one of the goto files.
+        std::fs::remove_file(goto_file).unwrap();
+        assert!(!fingerprint.is_fresh("args", &sources));
+
+        // Or the metadata files.
+        std::fs::remove_file(metadata_file).unwrap();
+        assert!(!fingerprint.is_fresh("args", &sources));
+    }
+
+    #[test]
+    fn check_collect_source_files_symlink_cycle() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+        std::os::unix::fs::symlink(dir, dir.join("src/cycle")).unwrap();
+
+        // Each directory is only walked once, even if it can be reached through a link.
+        let mut sources = vec![];
+        collect_source_files(&dir, &[], &mut HashSet::new(), &mut sources).unwrap();
+        assert_eq!(sources, [dir.join("src/lib.rs")]);
+    }
+
+    /// Create a workspace with the given files, and return its cargo metadata.
+    fn mock_workspace(dir: &Path, files: &[(&str, &str)]) -> cargo_metadata::Metadata {
+        for (file, content) in files {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        cargo_metadata::MetadataCommand::new()
+            .manifest_path(dir.join("Cargo.toml"))
+            .other_options(vec!["--offline".to_string()])
+            .exec()
+            .unwrap()
+    }
+
+    #[test]
+    fn check_source_digests_include_dependencies() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        let metadata = mock_workspace(
+            &dir,
+            &[
+                ("Cargo.toml", "[workspace]\nmembers = [\"app\", \"dep\", \"unused\"]\n"),
+                (
+                    "app/Cargo.toml",
+                    "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n\
+                     [dependencies]\ndep = { path = \"../dep\" }\n",
+                ),
+                ("app/src/lib.rs", ""),
+                ("dep/Cargo.toml", "[package]\nname = \"dep\"\nversion = \"0.1.0\"\n"),
+                ("dep/src/lib.rs", "include!(\"data.in\");"),
+                ("dep/src/data.in", ""),
+                ("unused/Cargo.toml", "[package]\nname = \"unused\"\nversion = \"0.1.0\"\n"),
+                ("unused/src/lib.rs", ""),
+            ],
+        );
+        let app = metadata.packages.iter().find(|package| package.name == "app").unwrap();
+        let target_dir = dir.join("target");
+
+        // Files of path dependencies are included, but not the ones of unrelated packages.
+        let sources = source_digests(&[app], &metadata, &target_dir, &BTreeMap::new()).unwrap();
+        assert!(sources.contains_key(&dir.join("app/src/lib.rs")));
+        assert!(sources.contains_key(&dir.join("dep/src/data.in")));
+        assert!(!sources.contains_key(&dir.join("unused/src/lib.rs")));
+
+        // Editing a file included by a dependency changes the digests.
+        std::fs::write(dir.join("dep/src/data.in"), "fn f() {}").unwrap();
+        let new_sources = source_digests(&[app], &metadata, &target_dir, &sources).unwrap();
+        let data = &dir.join("dep/src/data.in");
+        assert_ne!(new_sources[data].digest, sources[data].digest);
+
+        // So does adding a cargo configuration file.
+        std::fs::create_dir_all(dir.join(".cargo")).unwrap();
+        std::fs::write(dir.join(".cargo/config.toml"), "[build]\n").unwrap();
+        let sources = source_digests(&[app], &metadata, &target_dir, &new_sources).unwrap();
+        assert!(sources.contains_key(&dir.join(".cargo/config.toml")));
+    }
+
+    #[test]
+    fn check_source_digests_root_package() {
+        let dir_tmp = temp_dir();
+        let dir = dir_tmp.path();
+        let metadata = mock_workspace(
+            &dir,
+            &[
+                (
+                    "Cargo.toml",
+                    "[package]\nname = \"root\"\nversion = \"0.1.0\"\n\n\
+                     [workspace]\nmembers = [\"member\"]\n",
+                ),
+                ("src/lib.rs", ""),
+                ("member/Cargo.toml", "[package]\nname = \"member\"\nversion = \"0.1.0\"\n"),
+                ("member/src/lib.rs", ""),
+            ],
+        );
+        let root = metadata.root_package().unwrap();
+        let target_dir = dir.join("target");
+
+        // Members nested in the root package directory are not part of the root package.
+        let sources = source_digests(&[root], &metadata, &target_dir, &BTreeMap::new()).unwrap();
+        assert!(sources.contains_key(&dir.join("src/lib.rs")));
+        assert!(!sources.contains_key(&dir.join("member/src/lib.rs")));
+
+        // Files that didn't change since the previous digests are not hashed again.
+        let lib = dir.join("src/lib.rs");
+        let mut previous = sources.clone();
+        previous.get_mut(&lib).unwrap().digest = "previous".to_string();
+        let sources = source_digests(&[root], &metadata, &target_dir, &previous).unwrap();
+        assert_eq!(sources[&lib].digest, "previous");
+    }
+}
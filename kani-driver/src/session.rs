@@ -5,8 +5,9 @@
 use crate::args::VerificationArgs;
 use crate::util::render_command;
 use anyhow::{bail, Context, Result};
+use once_cell::sync::OnceCell;
 use std::io::IsTerminal;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::Mutex;
@@ -35,9 +36,18 @@ pub struct KaniSession {
     pub kani_compiler: PathBuf,
     /// The location we found 'kani_lib.c'
     pub kani_lib_c: PathBuf,
+    /// The `goto-cc` command used to link and specialize goto binaries
+    pub goto_cc: PathBuf,
+    /// The `goto-instrument` command used to transform goto binaries
+    pub goto_instrument: PathBuf,
+    /// The version reported by `goto-cc`, which is only queried when needed
+    pub goto_cc_version: OnceCell<String>,
 
     /// The temporary files we littered that need to be cleaned up at the end of execution
     pub temporaries: Mutex<Vec<PathBuf>>,
+    /// The temporary directories we created that need to be deleted with all their content at the
+    /// end of execution
+    pub temporary_dirs: Mutex<Vec<PathBuf>>,
 }
 
 /// Represents where we detected Kani, with helper methods for using that information to find critical paths
@@ -60,12 +70,19 @@ pub fn new(args: VerificationArgs) -> Result<Self> {
             codegen_tests: false,
             kani_compiler: install.kani_compiler()?,
             kani_lib_c: install.kani_lib_c()?,
+            goto_cc: PathBuf::from("goto-cc"),
+            goto_instrument: PathBuf::from("goto-instrument"),
+            goto_cc_version: OnceCell::new(),
             temporaries: Mutex::new(vec![]),
+            temporary_dirs: Mutex::new(vec![]),
         })
     }
 
     /// Record a temporary file so we can cleanup after ourselves at the end.
     /// Note that there will be no failure if the file does not exist.
+    ///
+    /// Directories can also be recorded, but they are only deleted if they are empty by the time
+    /// all the files recorded after them have been deleted.
     pub fn record_temporary_file<T: AsRef<Path>>(&self, temp: &T) {
         self.record_temporary_files(&[temp])
     }
@@ -79,12 +96,18 @@ pub fn record_temporary_files<T: AsRef<Path>>(&self, temps: &[T]) {
         t.extend(temps.iter().map(|p| p.as_ref().to_owned()));
     }
 
+    /// Record a temporary directory that is deleted with all its content at the end, even if it
+    /// includes files that were not recorded, e.g., because a build failed midway.
+    pub fn record_temporary_dir(&self, dir: &Path) {
+        self.temporary_dirs.lock().unwrap().push(dir.to_owned());
+    }
+
     /// Determine which symbols Kani should codegen (i.e. by slicing away symbols
     /// that are considered unreachable.)
     pub fn reachability_mode(&self) -> ReachabilityMode {
         if self.codegen_tests {
             ReachabilityMode::Tests
-        } else if self.args.function.is_some() {
+        } else if !self.args.function.is_empty() {
             ReachabilityMode::AllPubFns
         } else {
             ReachabilityMode::ProofHarnesses
@@ -107,9 +130,17 @@ fn drop(&mut self) {
         if !self.args.keep_temps {
             let temporaries = self.temporaries.lock().unwrap();
 
-            for file in temporaries.iter() {
+            // Delete in reverse order so directories are deleted after their content.
+            for file in temporaries.iter().rev() {
                 // If it fails, we don't care, skip it
-                let _result = std::fs::remove_file(file);
+                let _result = if file.is_dir() {
+                    std::fs::remove_dir(file)
+                } else {
+                    std::fs::remove_file(file)
+                };
+            }
+            for dir in self.temporary_dirs.lock().unwrap().iter() {
+                let _result = std::fs::remove_dir_all(dir);
             }
         }
     }
@@ -126,6 +157,11 @@ pub fn run_suppress(&self, cmd: Command) -> Result<()> {
         run_suppress(&self.args.common_args, cmd)
     }
 
+    /// Call [run_capture] with the verbosity configured by the user.
+    pub fn run_capture(&self, cmd: Command) -> Result<String> {
+        run_capture(&self.args.common_args, cmd)
+    }
+
     /// Call [run_redirect] with the verbosity configured by the user.
     pub fn run_redirect(&self, cmd: Command, stdout: &Path) -> Result<ExitStatus> {
         run_redirect(&self.args.common_args, cmd, stdout)
@@ -151,11 +187,12 @@ pub fn with_timer<T, F>(&self, func: F, description: &str) -> T
 // (In other words: higher-level data structures, rather than passing around Commands.)
 // (e.g. to support emitting Litani build graphs, or to better parallelize our work)
 
-// We basically have three different output policies:
+// We basically have four different output policies:
 //               No error                  Error                     Notes
 //               Default  Quiet  Verbose   Default  Quiet  Verbose
 // run_terminal  Y        N      Y         Y        N      Y         (inherits terminal)
 // run_suppress  N        N      Y         Y        N      Y         (buffered text only)
+// run_capture   N        N      Y         Y        Y      Y         (always captured: returned to the caller, or part of the error)
 // run_redirect  (not applicable, always to the file)                (only option where error is acceptable)
 
 /// Run a job, leave it outputting to terminal (unless --quiet), and fail if there's a problem.
@@ -203,6 +240,62 @@ pub fn run_suppress(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()>
     Ok(())
 }
 
+/// Run a job capturing both its stdout and stderr, and fail if there's a problem.
+/// The captured output is returned so the caller can decide what to do with it. In case of
+/// failure, the captured output is included in the error message instead, even with `--quiet`.
+///
+/// In verbose mode, the output is also printed to the terminal as the job runs.
+pub fn run_capture(verbosity: &impl Verbosity, mut cmd: Command) -> Result<String> {
+    let verbose = verbosity.verbose();
+    if verbose {
+        println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
+    }
+    let program = cmd.get_program().to_string_lossy().to_string();
+    let (status, stdout, stderr) = with_timer(
+        verbosity,
+        || -> Result<_> {
+            let mut child = cmd
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .context(format!("Failed to invoke {program}"))?;
+            let stdout = child.stdout.take().unwrap();
+            let stderr = child.stderr.take().unwrap();
+            // Both streams must be consumed concurrently, or the job may block on a full pipe.
+            let (stdout, stderr) = std::thread::scope(|scope| {
+                let stderr = scope.spawn(|| tee(stderr, verbose.then(std::io::stderr)));
+                (tee(stdout, verbose.then(std::io::stdout)), stderr.join().unwrap())
+            });
+            Ok((child.wait()?, stdout?, stderr?))
+        },
+        &program,
+    )?;
+    let mut output = stdout;
+    output.push_str(&stderr);
+    if !status.success() {
+        bail!("{program} exited with status {status}\n{output}");
+    }
+    Ok(output)
+}
+
+/// Read `input` until the end, copying everything that is read to `terminal` if there is one.
+fn tee(mut input: impl Read, mut terminal: Option<impl Write>) -> std::io::Result<String> {
+    let mut captured = vec![];
+    let mut buffer = [0; 8192];
+    loop {
+        let read = input.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        if let Some(terminal) = &mut terminal {
+            terminal.write_all(&buffer[..read])?;
+            terminal.flush()?;
+        }
+        captured.extend_from_slice(&buffer[..read]);
+    }
+    Ok(String::from_utf8_lossy(&captured).into_owned())
+}
+
 /// Run a job, redirect its output to a file, and allow the caller to decide what to do with failure.
 pub fn run_redirect(
     verbosity: &impl Verbosity,
@@ -379,3 +472,66 @@ fn init_logger(args: &VerificationArgs) {
     );
     tracing::subscriber::set_global_default(subscriber).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verbosity used for tests that shouldn't print anything.
+    struct Quiet;
+
+    impl Verbosity for Quiet {
+        fn quiet(&self) -> bool {
+            true
+        }
+        fn verbose(&self) -> bool {
+            false
+        }
+        fn debug(&self) -> bool {
+            false
+        }
+        fn is_set(&self) -> bool {
+            true
+        }
+    }
+
+    /// Verbosity used for tests that check the behavior with `--verbose`.
+    struct Verbose;
+
+    impl Verbosity for Verbose {
+        fn quiet(&self) -> bool {
+            false
+        }
+        fn verbose(&self) -> bool {
+            true
+        }
+        fn debug(&self) -> bool {
+            false
+        }
+        fn is_set(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn check_run_capture_output() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo linked; echo 'warning: unused symbol' >&2"]);
+        let output = run_capture(&Quiet, cmd).unwrap();
+        assert_eq!(output, "linked\nwarning: unused symbol\n");
+
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo 'error: missing symbol' >&2; exit 1"]);
+        let error = run_capture(&Quiet, cmd).unwrap_err().to_string();
+        assert!(error.contains("error: missing symbol"));
+
+        // In verbose mode, the output is also printed, but it is still captured.
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo linked; echo 'warning: unused symbol' >&2"]);
+        assert_eq!(run_capture(&Verbose, cmd).unwrap(), "linked\nwarning: unused symbol\n");
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo 'error: missing symbol' >&2; exit 1"]);
+        let error = run_capture(&Verbose, cmd).unwrap_err().to_string();
+        assert!(error.contains("error: missing symbol"));
+    }
+}
@@ -10,6 +10,8 @@
 //! to use the Rust compiler's error message utilities if you're working on the
 //! `kani-compiler`.
 
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -104,6 +106,26 @@ pub fn crate_name(path: &Path) -> String {
     stem.replace(['-', '.'], "_")
 }
 
+/// Compute a digest of the content of the given file.
+///
+/// The digest is the SHA-256 hash of the file, which is stable across Kani and Rust versions, so
+/// it can be stored and compared in later runs.
+pub fn file_digest(path: &Path) -> Result<String> {
+    let content =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(content)))
+}
+
+/// Build a thread pool with the given number of threads, or with one thread per available core if
+/// no number is given.
+pub fn thread_pool(num_threads: Option<usize>) -> Result<rayon::ThreadPool> {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(x) = num_threads {
+        builder = builder.num_threads(x);
+    }
+    Ok(builder.build()?)
+}
+
 /// Attempt to guess the rlib name for rust source file.
 /// This is only used by 'kani', never 'cargo-kani', so we hopefully don't have too many corner
 /// cases to deal with.
@@ -224,4 +246,15 @@ fn check_render_command() {
         c1.env("PARAM", "VALUE");
         assert_eq!(render_command(&c1), OsString::from("PARAM=\"VALUE\" a b \"/c d/\""));
     }
+
+    #[test]
+    fn check_file_digest() {
+        let path = std::env::temp_dir().join(format!("kani_digest_{}", rand::random::<u32>()));
+        std::fs::write(&path, "kani").unwrap();
+        assert_eq!(
+            file_digest(&path).unwrap(),
+            "69d24ccf6ccca405031ca8f03a611b9c92abf17b612b01253992f6564ea82773"
+        );
+        std::fs::remove_file(path).unwrap();
+    }
 }
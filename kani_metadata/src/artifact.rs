@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! Represent information about an artifact type.
 
+use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 
 /// Represent the type of an artifact generated by Kani and the corresponding extension.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum ArtifactType {
     /// A complete goto model generated after linking.
     Goto,
@@ -50,6 +51,9 @@ const fn extension(&self) -> &'static str {
 /// let goto = convert_type(&path, ArtifactType::SymTabGoto, ArtifactType::Goto);
 /// assert_eq!(goto.as_os_str(), "my_file.out");
 /// ```
+///
+/// For any path with the extension of `from`, the conversion can be reverted, i.e.:
+/// `convert_type(&convert_type(path, from, to), to, from) == path`.
 pub fn convert_type(path: &Path, from: ArtifactType, to: ArtifactType) -> PathBuf {
     let mut result = path.to_path_buf();
     // Strip current extensions and replace by the new one.
@@ -94,9 +98,12 @@ fn deref(&self) -> &Self::Target {
 
 #[cfg(test)]
 mod test {
-    use super::{convert_type, ArtifactType::*};
+    use super::{convert_type, ArtifactType, ArtifactType::*};
     use std::path::PathBuf;
 
+    const ALL_TYPES: [ArtifactType; 7] =
+        [Goto, Metadata, SymTab, SymTabGoto, TypeMap, VTableRestriction, PrettyNameMap];
+
     #[test]
     fn test_convert_ok() {
         let path = PathBuf::from("/tmp/my_file.rs").with_extension(&SymTabGoto);
@@ -107,6 +114,20 @@ fn test_convert_ok() {
         assert_eq!(orig, path);
     }
 
+    #[test]
+    fn test_convert_round_trip() {
+        for base in ["my_file", "/tmp/my_file", "/tmp/my.file", "/tmp/krate-1a2b.harness.name"] {
+            for from in ALL_TYPES {
+                let path = PathBuf::from(format!("{base}.{}", from.extension()));
+                for to in ALL_TYPES {
+                    let converted = convert_type(&path, from, to);
+                    assert_eq!(converted, PathBuf::from(format!("{base}.{}", to.extension())));
+                    assert_eq!(convert_type(&converted, to, from), path);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_set_extension_ok() {
         let path = PathBuf::from("/tmp/my_file.rs").with_extension(&SymTabGoto);
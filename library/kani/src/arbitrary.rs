@@ -5,8 +5,12 @@
 //! other std containers.
 
 use std::{
+    cmp::{Ordering, Reverse},
     marker::{PhantomData, PhantomPinned},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     num::*,
+    ops::{ControlFlow, Range, RangeInclusive},
+    time::{Duration, SystemTime},
 };
 
 /// This trait should be used to generate symbolic variables that represent any valid value of
@@ -121,6 +125,45 @@ fn any() -> Self {
 nonzero_arbitrary!(NonZeroI128, i128);
 nonzero_arbitrary!(NonZeroIsize, isize);
 
+impl<T> Arbitrary for Wrapping<T>
+where
+    T: Arbitrary,
+{
+    fn any() -> Self {
+        Wrapping(T::any())
+    }
+}
+
+impl<T> Arbitrary for Saturating<T>
+where
+    T: Arbitrary,
+{
+    fn any() -> Self {
+        Saturating(T::any())
+    }
+}
+
+impl Arbitrary for Ordering {
+    fn any() -> Self {
+        let selector = u8::any();
+        crate::assume(selector < 3);
+        match selector {
+            0 => Ordering::Less,
+            1 => Ordering::Equal,
+            _ => Ordering::Greater,
+        }
+    }
+}
+
+impl<T> Arbitrary for Reverse<T>
+where
+    T: Arbitrary,
+{
+    fn any() -> Self {
+        Reverse(T::any())
+    }
+}
+
 impl<T, const N: usize> Arbitrary for [T; N]
 where
     T: Arbitrary,
@@ -150,6 +193,16 @@ fn any() -> Self {
     }
 }
 
+impl<B, C> Arbitrary for ControlFlow<B, C>
+where
+    B: Arbitrary,
+    C: Arbitrary,
+{
+    fn any() -> Self {
+        if bool::any() { ControlFlow::Continue(C::any()) } else { ControlFlow::Break(B::any()) }
+    }
+}
+
 impl<T: ?Sized> Arbitrary for std::marker::PhantomData<T> {
     fn any() -> Self {
         PhantomData
@@ -170,3 +223,122 @@ fn any() -> Self {
         Box::new(T::any())
     }
 }
+
+impl<T> Arbitrary for std::pin::Pin<Box<T>>
+where
+    T: Arbitrary,
+{
+    fn any() -> Self {
+        Box::pin(T::any())
+    }
+}
+
+impl<T> Arbitrary for std::rc::Rc<T>
+where
+    T: Arbitrary,
+{
+    fn any() -> Self {
+        std::rc::Rc::new(T::any())
+    }
+}
+
+impl<T> Arbitrary for std::sync::Arc<T>
+where
+    T: Arbitrary,
+{
+    fn any() -> Self {
+        std::sync::Arc::new(T::any())
+    }
+}
+
+/// Generate a range where the start is never greater than the end.
+impl<T> Arbitrary for Range<T>
+where
+    T: Arbitrary + PartialOrd,
+{
+    fn any() -> Self {
+        let start = T::any();
+        let end = T::any();
+        crate::assume(start <= end);
+        start..end
+    }
+}
+
+/// Generate a range where the start is never greater than the end.
+impl<T> Arbitrary for RangeInclusive<T>
+where
+    T: Arbitrary + PartialOrd,
+{
+    fn any() -> Self {
+        let start = T::any();
+        let end = T::any();
+        crate::assume(start <= end);
+        start..=end
+    }
+}
+
+/// Generate a well-formed `Duration`, i.e., one where the sub-second nanoseconds are always
+/// smaller than one second.
+impl Arbitrary for Duration {
+    fn any() -> Self {
+        const NANOS_PER_SEC: u32 = 1_000_000_000;
+        let nanos = u32::any();
+        crate::assume(nanos < NANOS_PER_SEC);
+        Duration::new(u64::any(), nanos)
+    }
+}
+
+/// Generate a `SystemTime` that is representable by the platform, i.e., any time that is an
+/// arbitrary (well-formed) `Duration` before or after the `UNIX_EPOCH`.
+impl Arbitrary for SystemTime {
+    fn any() -> Self {
+        let offset = Duration::any();
+        let time = if bool::any() {
+            SystemTime::UNIX_EPOCH.checked_add(offset)
+        } else {
+            SystemTime::UNIX_EPOCH.checked_sub(offset)
+        };
+        crate::assume(time.is_some());
+        time.unwrap()
+    }
+}
+
+impl Arbitrary for Ipv4Addr {
+    fn any() -> Self {
+        Ipv4Addr::from(<[u8; 4]>::any())
+    }
+}
+
+impl Arbitrary for Ipv6Addr {
+    fn any() -> Self {
+        Ipv6Addr::from(<[u16; 8]>::any())
+    }
+}
+
+impl Arbitrary for IpAddr {
+    fn any() -> Self {
+        if bool::any() { IpAddr::V4(Ipv4Addr::any()) } else { IpAddr::V6(Ipv6Addr::any()) }
+    }
+}
+
+impl Arbitrary for SocketAddrV4 {
+    fn any() -> Self {
+        SocketAddrV4::new(Ipv4Addr::any(), u16::any())
+    }
+}
+
+impl Arbitrary for SocketAddrV6 {
+    fn any() -> Self {
+        SocketAddrV6::new(Ipv6Addr::any(), u16::any(), u32::any(), u32::any())
+    }
+}
+
+impl Arbitrary for SocketAddr {
+    fn any() -> Self {
+        if bool::any() {
+            SocketAddr::V4(SocketAddrV4::any())
+        } else {
+            SocketAddr::V6(SocketAddrV6::any())
+        }
+    }
+}
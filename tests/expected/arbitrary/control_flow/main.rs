@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Ensure that kani::any can generate both variants of ControlFlow.
+
+use std::ops::ControlFlow;
+
+#[kani::proof]
+fn check_control_flow() {
+    let flow: ControlFlow<u8, u16> = kani::any();
+    kani::cover!(flow.is_continue(), "Continue");
+    kani::cover!(flow.is_break(), "Break");
+    kani::cover!(flow == ControlFlow::Break(u8::MAX), "Break with max value");
+    kani::cover!(flow == ControlFlow::Continue(u16::MAX), "Continue with max value");
+}
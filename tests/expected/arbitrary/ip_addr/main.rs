@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Ensure that kani::any can generate any IP address.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[kani::proof]
+fn check_ip_addr() {
+    let ip: IpAddr = kani::any();
+    kani::cover!(ip.is_ipv4(), "IPv4 address");
+    kani::cover!(ip.is_ipv6(), "IPv6 address");
+    kani::cover!(ip == IpAddr::V4(Ipv4Addr::LOCALHOST), "IPv4 localhost");
+    kani::cover!(ip == IpAddr::V6(Ipv6Addr::LOCALHOST), "IPv6 localhost");
+}
+
+#[kani::proof]
+fn check_ipv4_addr() {
+    let ip: Ipv4Addr = kani::any();
+    assert_eq!(Ipv4Addr::from(ip.octets()), ip);
+    kani::cover!(ip.is_broadcast(), "Broadcast address");
+}
+
+#[kani::proof]
+fn check_ipv6_addr() {
+    let ip: Ipv6Addr = kani::any();
+    assert_eq!(Ipv6Addr::from(ip.segments()), ip);
+    kani::cover!(ip.is_unspecified(), "Unspecified address");
+}
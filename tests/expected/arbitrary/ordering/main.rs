@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Ensure that kani::any can generate any Ordering and any Reverse value.
+
+use std::cmp::{Ordering, Reverse};
+
+#[kani::proof]
+fn check_ordering() {
+    let ordering: Ordering = kani::any();
+    kani::cover!(ordering.is_lt(), "Less");
+    kani::cover!(ordering.is_eq(), "Equal");
+    kani::cover!(ordering.is_gt(), "Greater");
+}
+
+#[kani::proof]
+fn check_reverse() {
+    let first: Reverse<u8> = kani::any();
+    let second: Reverse<u8> = kani::any();
+    assert_eq!(first.cmp(&second), second.0.cmp(&first.0));
+    kani::cover!(first < second, "Reverse order");
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Ensure that kani::any can generate any socket address.
+
+use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6};
+
+#[kani::proof]
+fn check_socket_addr() {
+    let addr: SocketAddr = kani::any();
+    kani::cover!(addr.is_ipv4() && addr.port() == u16::MAX, "IPv4 address with max port");
+    kani::cover!(addr.is_ipv6() && addr.port() == u16::MAX, "IPv6 address with max port");
+    kani::cover!(addr.is_ipv4() && addr.ip().is_loopback(), "IPv4 loopback");
+    kani::cover!(addr.is_ipv6() && addr.ip().is_loopback(), "IPv6 loopback");
+}
+
+#[kani::proof]
+fn check_socket_addr_v4() {
+    let addr: SocketAddrV4 = kani::any();
+    assert_eq!(SocketAddrV4::new(*addr.ip(), addr.port()), addr);
+}
+
+#[kani::proof]
+fn check_socket_addr_v6() {
+    let addr: SocketAddrV6 = kani::any();
+    assert_eq!(SocketAddrV6::new(*addr.ip(), addr.port(), addr.flowinfo(), addr.scope_id()), addr);
+}
@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Ensure that kani::any generates each element of a tuple independently.
+
+#[kani::proof]
+fn check_tuple() {
+    let (a, b, c): (u8, u16, bool) = kani::any();
+    kani::cover!(a == u8::MAX && b == 0 && c, "First element may be MAX");
+    kani::cover!(a == 0 && b == u16::MAX && !c, "Second element may be MAX");
+    kani::cover!(a as u16 == b, "Elements may be equal");
+    kani::cover!(a as u16 != b, "Elements may differ");
+}
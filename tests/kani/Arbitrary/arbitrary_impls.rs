@@ -28,3 +28,10 @@ fn check_any_char() {
     let c: char = kani::any();
     assert!(c <= char::MAX);
 }
+
+#[kani::proof]
+fn check_any_char_is_scalar() {
+    let c: char = kani::any();
+    assert!(!(0xD800..=0xDFFF).contains(&(c as u32)));
+    assert_eq!(char::from_u32(c as u32), Some(c));
+}
@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the Arbitrary implementation for Duration only generates well-formed durations.
+
+use std::time::Duration;
+
+#[kani::proof]
+fn check_any_duration() {
+    let duration: Duration = kani::any();
+    assert!(duration.subsec_nanos() < 1_000_000_000);
+    assert_eq!(Duration::new(duration.as_secs(), duration.subsec_nanos()), duration);
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the Arbitrary implementations for the arithmetic wrappers generate symbolic values.
+
+use std::num::{Saturating, Wrapping};
+
+#[kani::proof]
+fn check_any_wrapping() {
+    let wrapping: Wrapping<u8> = kani::any();
+    let sum = wrapping + Wrapping(1);
+    assert!(sum.0 == 0 || sum.0 > wrapping.0);
+    kani::cover!(sum.0 == 0);
+}
+
+#[kani::proof]
+fn check_any_saturating() {
+    let saturating: Saturating<i8> = kani::any();
+    let sum = saturating + Saturating(1);
+    assert!(sum.0 == i8::MAX || sum.0 > saturating.0);
+    kani::cover!(saturating.0 == i8::MAX);
+}
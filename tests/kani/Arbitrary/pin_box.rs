@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that kani::any can generate pinned boxed values, including values that are `!Unpin`.
+
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+#[derive(kani::Arbitrary)]
+struct SelfReferential {
+    value: u8,
+    _pinned: PhantomPinned,
+}
+
+#[kani::proof]
+fn check_any_pin_box() {
+    let pinned: Pin<Box<u8>> = kani::any();
+    let value = *pinned;
+    assert_eq!(*Pin::into_inner(pinned), value);
+}
+
+#[kani::proof]
+fn check_any_pin_box_not_unpin() {
+    let pinned: Pin<Box<SelfReferential>> = kani::any();
+    let value = pinned.value;
+    assert_eq!(pinned.as_ref().get_ref().value, value);
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the Arbitrary implementation for ranges only generates ranges where the start is
+//! not greater than the end.
+
+use std::ops::{Range, RangeInclusive};
+
+#[kani::proof]
+fn check_any_range() {
+    let range: Range<u8> = kani::any();
+    assert!(range.start <= range.end);
+    assert_eq!(range.len(), (range.end - range.start) as usize);
+}
+
+#[kani::proof]
+fn check_any_range_inclusive() {
+    let range: RangeInclusive<i8> = kani::any();
+    assert!(range.start() <= range.end());
+    assert!(!range.is_empty());
+}
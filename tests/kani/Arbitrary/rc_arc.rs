@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that kani::any can generate reference-counted values, and that the new pointer is the
+//! only owner of its value.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[kani::proof]
+fn check_any_rc() {
+    let rc: Rc<u32> = kani::any();
+    assert_eq!(Rc::strong_count(&rc), 1);
+    let value = *rc;
+    assert_eq!(Rc::try_unwrap(rc).ok(), Some(value));
+}
+
+#[kani::proof]
+fn check_any_arc() {
+    let arc: Arc<u32> = kani::any();
+    assert_eq!(Arc::strong_count(&arc), 1);
+    let value = *arc;
+    assert_eq!(Arc::try_unwrap(arc).ok(), Some(value));
+}
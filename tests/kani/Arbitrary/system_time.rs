@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the Arbitrary implementation for SystemTime only generates times with well-formed
+//! nanoseconds, which can be safely combined with durations.
+
+use std::time::{Duration, SystemTime};
+
+#[kani::proof]
+fn check_any_system_time() {
+    let time: SystemTime = kani::any();
+    let offset = match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(offset) => offset,
+        Err(err) => err.duration(),
+    };
+    assert!(offset.subsec_nanos() < 1_000_000_000);
+}
+
+#[kani::proof]
+fn check_add_sub_system_time() {
+    let time: SystemTime = kani::any();
+    let duration: Duration = kani::any();
+    if let Some(later) = time.checked_add(duration) {
+        assert_eq!(later.duration_since(time).unwrap(), duration);
+    }
+}
@@ -34,15 +34,15 @@ rm -rf *.c
 kani --gen-c --enable-unstable singlefile.rs >& kani.log || \
     { ret=$?; echo "== Failed to run Kani"; cat kani.log; rm kani.log; exit 1; }
 rm -f kani.log
-if ! [ -e singlefile_main.c ]
+if ! [ -e singlefile_main.main.c ]
 then
-    echo "Error: no GotoC file generated. Expected: singlefile_main.c"
+    echo "Error: no GotoC file generated. Expected: singlefile_main.main.c"
     exit 1
 fi
 
-if ! [ -e singlefile_main.demangled.c ]
+if ! [ -e singlefile_main.main.demangled.c ]
 then
-    echo "Error: no demangled GotoC file generated. Expected singlefile_main.demangled.c."
+    echo "Error: no demangled GotoC file generated. Expected singlefile_main.main.demangled.c."
     exit 1
 fi
 
@@ -57,9 +57,9 @@ declare -a PATTERNS=(
 )
 
 for val in "${PATTERNS[@]}"; do
-    if ! grep -Fq "$val" singlefile_main.demangled.c;
+    if ! grep -Fq "$val" singlefile_main.main.demangled.c;
     then
-        echo "Error: demangled file singlefile_main.demangled.c did not contain expected pattern '$val'."
+        echo "Error: demangled file singlefile_main.main.demangled.c did not contain expected pattern '$val'."
         exit 1
     fi
 done
@@ -75,17 +75,17 @@ cargo kani --target-dir build --gen-c --enable-unstable >& kani.log || \
 rm -f kani.log
 cd build/kani/${TARGET}/debug/deps/
 
-mangled=$(ls multifile*_main.c)
+mangled=$(ls multifile*_main.main.c)
 if ! [ -e "${mangled}" ]
 then
-    echo "Error: no GotoC file found. Expected: build/kani/${TARGET}/debug/deps/multifile*_main.c"
+    echo "Error: no GotoC file found. Expected: build/kani/${TARGET}/debug/deps/multifile*_main.main.c"
     exit 1
 fi
 
-demangled=$(ls multifile*_main.demangled.c)
+demangled=$(ls multifile*_main.main.demangled.c)
 if ! [ -e "${demangled}" ]
 then
-    echo "Error: no demangled GotoC file found. Expected build/kani/${TARGET}/debug/deps/multifile*_main.demangled.c."
+    echo "Error: no demangled GotoC file found. Expected build/kani/${TARGET}/debug/deps/multifile*_main.main.demangled.c."
     exit 1
 fi
 